@@ -0,0 +1,66 @@
+//! Compares the signature matchers on a buffer of pseudo-random machine code:
+//! the `data_compare` loop the scanner started with, `Pattern::matches` at every
+//! position, and the anchored `Pattern::find`.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use gamehack_librs::{Pattern, utils::data_compare};
+
+const SIZE: usize = 16 << 20;
+const SIGNATURE: &[u8] = b"\x48\x8D\x05\x7A\xB9\xA6\x01\x48\x89\x41\x18\x49\xBF";
+const MASK: &str = "xxx????xxxxxx";
+
+/// Fills a buffer with bytes distributed like code: mostly REX prefixes, `mov`s,
+/// zeros and `int3` padding, with the signature at the very end.
+fn haystack() -> Vec<u8> {
+    const COMMON: &[u8] = &[0x00, 0x48, 0x89, 0x8B, 0x8D, 0xE8, 0xCC, 0x0F, 0x24, 0xFF];
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut data = (0..SIZE)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            match state % 4 {
+                0 => state.to_le_bytes()[3],
+                _ => COMMON[(state >> 8) as usize % COMMON.len()],
+            }
+        })
+        .collect::<Vec<_>>();
+    data[SIZE - SIGNATURE.len()..].copy_from_slice(SIGNATURE);
+    data
+}
+
+fn scan(c: &mut Criterion) {
+    let data = haystack();
+    let pattern = Pattern::from_code(SIGNATURE, MASK).unwrap();
+    let expected = Some(SIZE - SIGNATURE.len());
+
+    let mut group = c.benchmark_group("scan 16 MiB");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(10);
+
+    group.bench_function("data_compare", |b| {
+        b.iter(|| {
+            let found = black_box(&data)
+                .windows(MASK.len())
+                .position(|window| data_compare(window, SIGNATURE, MASK));
+            assert_eq!(found, expected);
+        });
+    });
+    group.bench_function("Pattern::matches", |b| {
+        b.iter(|| {
+            let found = black_box(&data)
+                .windows(pattern.len())
+                .position(|window| pattern.matches(window));
+            assert_eq!(found, expected);
+        });
+    });
+    group.bench_function("Pattern::find", |b| {
+        b.iter(|| assert_eq!(pattern.find(black_box(&data)), expected));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scan);
+criterion_main!(benches);
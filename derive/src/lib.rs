@@ -0,0 +1,199 @@
+//! Derive macros of `gamehack_librs`. Use them through the re-exports of the main
+//! crate, e.g. `gamehack_librs::RemoteStruct`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitInt, parse_macro_input, spanned::Spanned};
+
+/// Implements `RemoteStruct` for a struct with named fields, so it can be read from
+/// the target with a single bulk read.
+///
+/// # Attributes
+///
+/// * `#[offset(0x18)]` - Required on every field: the offset of the field from the
+///   start of the struct in the target.
+/// * `#[pointer]` - The field holds a pointer in the target. The struct it points to
+///   is read as well; the field type must implement `RemoteStruct`, or be an
+///   `Option` of such a type to allow null pointers. A `RemotePtr` field is read
+///   with the target's pointer width but not followed.
+///
+/// Fields without `#[pointer]` must implement `Pod`.
+#[proc_macro_derive(RemoteStruct, attributes(offset, pointer))]
+pub fn derive_remote_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    remote_struct(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `Pod` for a `#[repr(C)]` or `#[repr(transparent)]` struct after
+/// checking the rules of the trait at compile time:
+///
+/// * the struct has a `C`, `transparent` or `packed` representation, so its layout
+///   is specified,
+/// * every field implements `Pod`,
+/// * the size of the struct is the sum of the sizes of its fields, so it has no
+///   padding.
+///
+/// Generic structs are not supported, since padding can only be checked for
+/// concrete field types.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    pod(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn pod(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "Pod can only be derived for structs",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Pod cannot be derived for generic structs",
+        ));
+    }
+
+    let mut specified = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C")
+                || meta.path.is_ident("transparent")
+                || meta.path.is_ident("packed")
+            {
+                specified = true;
+            }
+            if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    if !specified {
+        return Err(Error::new(
+            input.ident.span(),
+            "Pod needs #[repr(C)], #[repr(transparent)] or #[repr(packed)]",
+        ));
+    }
+
+    let name = &input.ident;
+    let types = data
+        .fields
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+    Ok(quote! {
+        const _: () = {
+            fn assert_pod<T: ::gamehack_librs::Pod>() {}
+            fn assert_fields() {
+                #(assert_pod::<#types>();)*
+            }
+            assert!(
+                ::core::mem::size_of::<#name>() == 0 #(+ ::core::mem::size_of::<#types>())*,
+                concat!("`", stringify!(#name), "` has padding bytes and cannot be Pod"),
+            );
+        };
+
+        unsafe impl ::gamehack_librs::Pod for #name {}
+    })
+}
+
+/// A field of the derived struct with its parsed attributes.
+struct Field<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a syn::Type,
+    offset: usize,
+    pointer: bool,
+}
+
+fn remote_struct(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "RemoteStruct can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(Error::new(
+            data.fields.span(),
+            "RemoteStruct needs named fields",
+        ));
+    };
+    let fields = named
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ends = fields.iter().map(|field| {
+        let Field { ty, offset, .. } = field;
+        if field.pointer {
+            quote!(#offset + pointer_width)
+        } else {
+            quote!(#offset + ::core::mem::size_of::<#ty>())
+        }
+    });
+    let decoders = fields.iter().map(|field| {
+        let Field {
+            ident, ty, offset, ..
+        } = field;
+        if field.pointer {
+            quote!(#ident: ::gamehack_librs::remote::pointer_field::<_, #ty>(memory, bytes, #offset, depth)?)
+        } else {
+            quote!(#ident: ::gamehack_librs::remote::field::<#ty>(bytes, #offset))
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::gamehack_librs::remote::RemoteStruct for #name #ty_generics #where_clause {
+            fn remote_size(pointer_width: usize) -> usize {
+                let _ = pointer_width;
+                0 #(.max(#ends))*
+            }
+
+            fn decode<M: ::gamehack_librs::MemoryAccess + ?Sized>(
+                memory: &M,
+                bytes: &[u8],
+                depth: usize,
+            ) -> ::core::result::Result<Self, ::gamehack_librs::Errors> {
+                let _ = (memory, depth);
+                ::core::result::Result::Ok(Self { #(#decoders,)* })
+            }
+        }
+    })
+}
+
+fn parse_field(field: &syn::Field) -> Result<Field<'_>, Error> {
+    let mut offset = None;
+    let mut pointer = false;
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("offset") {
+            if offset.is_some() {
+                return Err(Error::new(attr.span(), "duplicate #[offset] attribute"));
+            }
+            offset = Some(attr.parse_args::<LitInt>()?.base10_parse::<usize>()?);
+        } else if attr.path().is_ident("pointer") {
+            attr.meta.require_path_only()?;
+            pointer = true;
+        }
+    }
+
+    Ok(Field {
+        ident: field.ident.as_ref().expect("named field"),
+        ty: &field.ty,
+        offset: offset
+            .ok_or_else(|| Error::new(field.span(), "missing #[offset(..)] attribute on field"))?,
+        pointer,
+    })
+}
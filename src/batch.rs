@@ -0,0 +1,186 @@
+use std::{marker::PhantomData, ops::Range, ptr};
+
+use crate::{
+    errors::{Errors, OsError},
+    memory::MemoryAccess,
+    pod::Pod,
+};
+
+/// A list of reads from scattered addresses that are executed together.
+///
+/// Overlays read hundreds of small fields per frame; reading them one by one costs a
+/// system call each. A `BatchRead` collects the requests once and reads all of them
+/// with [`MemoryAccess::read_scattered`], which needs only a few calls. Every request
+/// succeeds or fails on its own.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::{batch::BatchRead, mock::MockProcess, types::Protection};
+///
+/// let process = MockProcess::new().region(0x1000, [0u8; 0x100], Protection::RW);
+/// process.poke(0x1010, &100u32.to_le_bytes());
+/// process.poke(0x1080, b"Agent 47\0");
+///
+/// let mut batch = BatchRead::new();
+/// let health = batch.push::<u32>(0x1010);
+/// let name = batch.push_bytes(0x1080, 8);
+/// let missing = batch.push::<u32>(0x9000);
+///
+/// let results = batch.read(&process);
+/// assert_eq!(results.get(health), Ok(100));
+/// assert_eq!(results.bytes(name), Ok(&b"Agent 47"[..]));
+/// assert!(results.get(missing).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BatchRead {
+    /// The address of every request and its range in the result buffer.
+    requests: Vec<(usize, Range<usize>)>,
+    len: usize,
+}
+
+/// Refers to one request of a [`BatchRead`] and the type it is read as.
+pub struct Slot<T: ?Sized> {
+    index: usize,
+    marker: PhantomData<fn() -> *const T>,
+}
+
+impl<T: ?Sized> Clone for Slot<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Slot<T> {}
+
+impl<T: ?Sized> std::fmt::Debug for Slot<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Slot").field(&self.index).finish()
+    }
+}
+
+impl BatchRead {
+    /// Creates an empty batch.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a read of a `T` at `address` and returns the slot to fetch it with.
+    pub fn push<T: Pod>(&mut self, address: usize) -> Slot<T> {
+        self.push_range(address, size_of::<T>())
+    }
+
+    /// Adds a read of `len` raw bytes at `address`, e.g. a name buffer.
+    pub fn push_bytes(&mut self, address: usize, len: usize) -> Slot<[u8]> {
+        self.push_range(address, len)
+    }
+
+    /// Returns the number of requests.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns `true` if the batch has no requests.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Executes every request against `memory`.
+    ///
+    /// The batch is left unchanged, so it can be built once and read every frame.
+    pub fn read<M: MemoryAccess + ?Sized>(&self, memory: &M) -> BatchResults {
+        let mut data = vec![0u8; self.len];
+        let mut requests = Vec::with_capacity(self.requests.len());
+        let mut rest = data.as_mut_slice();
+        for (address, range) in &self.requests {
+            let (buffer, tail) = rest.split_at_mut(range.len());
+            requests.push((*address, buffer));
+            rest = tail;
+        }
+
+        let results = memory
+            .read_scattered(&mut requests)
+            .into_iter()
+            .zip(&self.requests)
+            .map(|(result, (address, range))| {
+                let (transferred, os_error) = match result {
+                    Ok(transferred) if transferred == range.len() => return Ok(()),
+                    Ok(transferred) => (transferred, None),
+                    Err(err) => (0, OsError::of(&err)),
+                };
+                Err(Errors::ReadFailed {
+                    pid: memory.pid(),
+                    address: *address,
+                    requested: range.len(),
+                    transferred,
+                    os_error,
+                })
+            })
+            .collect();
+
+        BatchResults {
+            data,
+            ranges: self
+                .requests
+                .iter()
+                .map(|(_, range)| range.clone())
+                .collect(),
+            results,
+        }
+    }
+
+    fn push_range<T: ?Sized>(&mut self, address: usize, len: usize) -> Slot<T> {
+        self.requests.push((address, self.len..self.len + len));
+        self.len += len;
+        Slot {
+            index: self.requests.len() - 1,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The outcome of [`BatchRead::read`], holding the data of every request.
+#[derive(Debug, Clone)]
+pub struct BatchResults {
+    data: Vec<u8>,
+    ranges: Vec<Range<usize>>,
+    results: Vec<Result<(), Errors>>,
+}
+
+impl BatchResults {
+    /// Returns the value read for `slot`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::ReadFailed`] if the request could not be read completely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slot` belongs to a different batch with more or other requests.
+    pub fn get<T: Pod>(&self, slot: Slot<T>) -> Result<T, Errors> {
+        let bytes = self.bytes_of(slot.index)?;
+        assert_eq!(bytes.len(), size_of::<T>(), "slot of a different batch");
+        Ok(unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<T>()) })
+    }
+
+    /// Returns the bytes read for `slot`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::ReadFailed`] if the request could not be read completely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slot` belongs to a different batch with more requests.
+    pub fn bytes(&self, slot: Slot<[u8]>) -> Result<&[u8], Errors> {
+        self.bytes_of(slot.index)
+    }
+
+    fn bytes_of(&self, index: usize) -> Result<&[u8], Errors> {
+        self.results[index].clone()?;
+        Ok(&self.data[self.ranges[index].clone()])
+    }
+}
@@ -0,0 +1,114 @@
+use std::{path::PathBuf, sync::OnceLock};
+
+use crate::{errors::Errors, platform, process::Process, types::Architecture};
+
+/// A lightweight record of a running process, as yielded by [`processes`](crate::processes).
+///
+/// Enumerating processes does not open them: the record only carries what the
+/// system lists for every process. The executable path and the architecture are
+/// looked up together on the first call to [`exe_path`](Self::exe_path) or
+/// [`architecture`](Self::architecture) and cached in the entry. On Windows that
+/// lookup opens the process once with `PROCESS_QUERY_LIMITED_INFORMATION` and
+/// closes it again; on Linux it reads `/proc/<pid>/exe`.
+#[derive(Debug, Clone)]
+pub struct ProcessEntry {
+    pid: u32,
+    parent_pid: u32,
+    name: String,
+    details: OnceLock<(Option<PathBuf>, Option<Architecture>)>,
+}
+
+impl ProcessEntry {
+    pub(crate) fn new(pid: u32, parent_pid: u32, name: String) -> Self {
+        Self {
+            pid,
+            parent_pid,
+            name,
+            details: OnceLock::new(),
+        }
+    }
+
+    /// Returns the process identifier (PID).
+    #[must_use]
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Returns the PID of the process that created this one, or 0 if unknown.
+    #[must_use]
+    pub fn parent_pid(&self) -> u32 {
+        self.parent_pid
+    }
+
+    /// Returns the executable name in its original case, e.g. `"Hitman3.exe"`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the full path of the executable, or `None` if it cannot be queried
+    /// (e.g. protected processes or processes of other users).
+    ///
+    /// The first call to this method or [`architecture`](Self::architecture)
+    /// queries the system, later calls return the cached result.
+    #[must_use]
+    pub fn exe_path(&self) -> Option<PathBuf> {
+        self.details().0.clone()
+    }
+
+    /// Returns the architecture the process runs as, or `None` if it cannot be queried.
+    ///
+    /// Shares the cached lookup of [`exe_path`](Self::exe_path).
+    #[must_use]
+    pub fn architecture(&self) -> Option<Architecture> {
+        self.details().1
+    }
+
+    fn details(&self) -> &(Option<PathBuf>, Option<Architecture>) {
+        self.details
+            .get_or_init(|| platform::process_details(self.pid))
+    }
+
+    /// Opens the process with every access right, see [`Process::open`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::ProcessNotFound`] if the process has exited in the meantime.
+    /// * [`Errors::AccessDenied`] if the OS refuses full access.
+    pub fn open(&self) -> Result<Process, Errors> {
+        Process::open(self.pid)
+    }
+}
+
+/// Compares the listed fields, whether or not the details have been looked up.
+impl PartialEq for ProcessEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.pid == other.pid && self.parent_pid == other.parent_pid && self.name == other.name
+    }
+}
+
+impl Eq for ProcessEntry {}
+
+/// Iterator over the processes running on the system, see [`processes`](crate::processes).
+///
+/// On Windows it walks a ToolHelp snapshot taken when the iterator was created, on
+/// Linux it reads `/proc` lazily, skipping processes that exit while iterating.
+pub struct Processes {
+    inner: platform::ProcessIter,
+}
+
+impl Processes {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: platform::processes(),
+        }
+    }
+}
+
+impl Iterator for Processes {
+    type Item = ProcessEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
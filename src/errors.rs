@@ -1,37 +1,326 @@
-use std::{ffi::FromBytesUntilNulError, fmt::Display, num::TryFromIntError, str::Utf8Error};
+use std::{
+    borrow::Cow, error::Error, ffi::FromBytesUntilNulError, fmt::Display, io, num::TryFromIntError,
+    ops::Range, str::Utf8Error,
+};
 
+use crate::{options::Access, strings::Encoding};
+
+/// Every error this crate reports.
+///
+/// The type owns all of its data, so it can be stored, sent across threads and
+/// converted into `Box<dyn Error>` or `anyhow::Error` with `?`. Memory errors carry
+/// the address, the operation (by variant), the PID of the target if the backend
+/// knows it and the OS error, which is also exposed through [`Error::source`].
 #[repr(C)]
-#[derive(Debug, PartialEq, Eq)]
-/// Enum full of errors :c
-pub enum Errors<'src> {
-    EmptyBuffer(&'src str),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Errors {
     ProcessNotFound,
     SignatureNotFound,
     NoNulByte(FromBytesUntilNulError),
     InvalidUtf8(Utf8Error),
     IntError(TryFromIntError),
+    /// Reading `requested` bytes at `address` stopped after `transferred` bytes.
+    ReadFailed {
+        pid: Option<u32>,
+        address: usize,
+        requested: usize,
+        transferred: usize,
+        os_error: Option<OsError>,
+    },
+    /// Writing `requested` bytes at `address` stopped after `transferred` bytes.
+    WriteFailed {
+        pid: Option<u32>,
+        address: usize,
+        requested: usize,
+        transferred: usize,
+        os_error: Option<OsError>,
+    },
+    /// A pointer path could not be parsed; holds the offending part of the input.
+    InvalidPointerPath(String),
+    /// The module a pointer path is relative to is not loaded.
+    ModuleNotFound(String),
+    /// Level `level` of a pointer path (0 = the base) points to the unreadable `address`.
+    BrokenPointerPath {
+        pid: Option<u32>,
+        level: usize,
+        address: usize,
+        os_error: Option<OsError>,
+    },
+    /// An address expression could not be parsed; holds the offending part of the input.
+    InvalidExpression(String),
+    /// A name in an address expression is neither a variable nor a loaded module.
+    UnknownSymbol(String),
+    /// The OS refused to open process `pid` with the requested rights. `access` is the
+    /// refused right if the platform can tell which one it was.
+    AccessDenied {
+        pid: u32,
+        access: Option<Access>,
+        os_error: Option<OsError>,
+    },
+    /// The operation needs a right the process was not opened with.
+    MissingAccess(Access),
+    /// Waiting for a process did not finish before the timeout.
+    Timeout,
+    /// Waiting for a process was cancelled.
+    Cancelled,
+    /// The target process has exited. `code` is its exit code if the OS reports it.
+    ProcessExited {
+        pid: u32,
+        code: Option<i32>,
+    },
+    /// The string at `address` has no terminator within its first `max_len` code units.
+    UnterminatedString {
+        address: usize,
+        max_len: usize,
+    },
+    /// The string at `address` is not valid in `encoding`, or a string to be written
+    /// there cannot be represented in it.
+    InvalidString {
+        address: usize,
+        encoding: Encoding,
+    },
+    /// A string needs `len` code units including the terminator, but its buffer only
+    /// holds `max_len`.
+    StringTooLong {
+        len: usize,
+        max_len: usize,
+    },
+    /// Some elements of the array at `address` lie on unreadable pages. `unreadable`
+    /// holds the ranges of their indices in ascending order.
+    UnreadableElements {
+        address: usize,
+        unreadable: Vec<Range<usize>>,
+    },
+    /// A signature pattern could not be parsed; holds the offending token.
+    InvalidPattern(String),
+    /// The byte string and the mask of a signature pattern differ in length.
+    PatternLengthMismatch {
+        bytes: usize,
+        mask: usize,
+    },
+    /// A signature pattern has no bytes.
+    EmptyPattern,
+    /// Reading a [`RemoteStruct`](crate::RemoteStruct) would follow the pointer to
+    /// `address` after `max_depth` others, e.g. because the pointers form a cycle.
+    PointerDepthExceeded {
+        address: usize,
+        max_depth: usize,
+    },
+    /// A signature that should be unique matched `matches` times.
+    AmbiguousSignature {
+        matches: usize,
+    },
+    /// An array of `count` elements of `element_size` bytes at `address` overflows
+    /// the address space or exceeds the memory mapped there, e.g. because the count
+    /// was read from a garbage value.
+    SliceTooLarge {
+        address: usize,
+        count: usize,
+        element_size: usize,
+    },
 }
 
+/// An OS error code (`errno` on Linux, `GetLastError` on Windows).
+///
+/// Kept as a plain code so [`Errors`] stays comparable; [`Display`] renders the
+/// system message, e.g. `Bad address (os error 14)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OsError(pub i32);
+
+impl OsError {
+    /// Returns the OS error code of `err`, if it carries one.
+    #[must_use]
+    pub fn of(err: &io::Error) -> Option<Self> {
+        err.raw_os_error().map(Self)
+    }
+
+    /// Returns the raw error code.
+    #[must_use]
+    pub fn code(self) -> i32 {
+        self.0
+    }
+}
+
+impl Display for OsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        io::Error::from_raw_os_error(self.0).fmt(f)
+    }
+}
+
+impl Error for OsError {}
+
 /// Provides a human-readable representation of [`Errors`].
 ///
 /// This implementation allows errors to be printed using the `{}` format specifier,
 /// which is essential for user-facing error messages and logging.
-impl Display for Errors<'_> {
+impl Display for Errors {
     /// Formats the error into a user-friendly string.
     ///
-    /// The resulting string is prefixed with `"Error: "` followed by a specific
-    /// message based on the error variant.
-    ///
+    /// The message describes the failed operation including the details of wrapped
+    /// errors. It carries no `"Error: "` prefix, since error reporters such as
+    /// `anyhow` add their own.
     fn fmt(&'_ self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let message = match &self {
-            Errors::EmptyBuffer(error) => error,
-            Errors::ProcessNotFound => "Process not found!",
-            Errors::SignatureNotFound => "Signature not found!",
-            Errors::NoNulByte(_) => "No nul byte was present",
-            Errors::InvalidUtf8(_) => "Attempt to interpret a sequence of u8 as a String failed",
-            Errors::IntError(_) => "The provided number is too large or too small to be processed",
+        let message: Cow<'_, str> = match &self {
+            Errors::ProcessNotFound => "Process not found!".into(),
+            Errors::SignatureNotFound => "Signature not found!".into(),
+            Errors::InvalidPattern(token) => format!("Invalid pattern near `{token}`").into(),
+            Errors::PatternLengthMismatch { bytes, mask } => {
+                format!("Pattern has {bytes} bytes but a mask of {mask} characters").into()
+            }
+            Errors::EmptyPattern => "Pattern is empty".into(),
+            Errors::PointerDepthExceeded { address, max_depth } => format!(
+                "Pointer to {address:#X} exceeds the maximum depth of {max_depth} pointers"
+            )
+            .into(),
+            Errors::AmbiguousSignature { matches } => {
+                format!("Signature is not unique: found {matches} matches").into()
+            }
+            Errors::SliceTooLarge {
+                address,
+                count,
+                element_size,
+            } => format!(
+                "Array of {count} elements of {element_size} bytes at {address:#X} exceeds the mapped memory"
+            )
+            .into(),
+            Errors::NoNulByte(err) => format!("No nul byte was present: {err}").into(),
+            Errors::InvalidUtf8(err) => {
+                format!("Attempt to interpret a sequence of u8 as a String failed: {err}").into()
+            }
+            Errors::IntError(err) => {
+                format!("The provided number is too large or too small to be processed: {err}")
+                    .into()
+            }
+            Errors::ReadFailed {
+                pid,
+                address,
+                requested,
+                transferred,
+                os_error,
+            } => {
+                transfer_message("read", *pid, *address, *requested, *transferred, *os_error).into()
+            }
+            Errors::WriteFailed {
+                pid,
+                address,
+                requested,
+                transferred,
+                os_error,
+            } => transfer_message("write", *pid, *address, *requested, *transferred, *os_error)
+                .into(),
+            Errors::InvalidPointerPath(part) => {
+                format!("Invalid pointer path near `{part}`").into()
+            }
+            Errors::ModuleNotFound(name) => format!("Module `{name}` not found!").into(),
+            Errors::BrokenPointerPath {
+                pid,
+                level,
+                address,
+                os_error,
+            } => {
+                let mut message = format!("Pointer path broken at level {level}: {address:#X}");
+                push_pid(&mut message, *pid);
+                message.push_str(" is unreadable");
+                push_os_error(&mut message, *os_error);
+                message.into()
+            }
+            Errors::InvalidExpression(part) => format!("Invalid expression near `{part}`").into(),
+            Errors::UnknownSymbol(name) => format!("Unknown symbol `{name}`").into(),
+            Errors::AccessDenied {
+                pid,
+                access,
+                os_error,
+            } => {
+                let mut message = format!("Access to process {pid} denied");
+                if let Some(access) = access {
+                    message.push_str(&format!(" ({access} right)"));
+                }
+                push_os_error(&mut message, *os_error);
+                message.into()
+            }
+            Errors::MissingAccess(access) => {
+                format!("Process was opened without the {access} right").into()
+            }
+            Errors::Timeout => "Timed out waiting for the process".into(),
+            Errors::Cancelled => "Waiting for the process was cancelled".into(),
+            Errors::ProcessExited { pid, code } => match code {
+                Some(code) => format!("Process {pid} exited with code {code}").into(),
+                None => format!("Process {pid} exited").into(),
+            },
+            Errors::UnterminatedString { address, max_len } => {
+                format!("String at {address:#X} is not terminated within {max_len} code units")
+                    .into()
+            }
+            Errors::InvalidString { address, encoding } => {
+                format!("String at {address:#X} is not valid {encoding}").into()
+            }
+            Errors::StringTooLong { len, max_len } => format!(
+                "String of {len} code units (including the terminator) exceeds the buffer of {max_len}"
+            )
+            .into(),
+            Errors::UnreadableElements {
+                address,
+                unreadable,
+            } => {
+                let ranges: Vec<String> = unreadable
+                    .iter()
+                    .map(|range| format!("{}..{}", range.start, range.end))
+                    .collect();
+                format!(
+                    "Elements {} of the array at {address:#X} are unreadable",
+                    ranges.join(", ")
+                )
+                .into()
+            }
         };
-        write!(f, "Error: {message}")
+        f.write_str(&message)
+    }
+}
+
+/// Chains the wrapped standard library error or the OS error of a failed operation.
+impl Error for Errors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Errors::NoNulByte(err) => Some(err),
+            Errors::InvalidUtf8(err) => Some(err),
+            Errors::IntError(err) => Some(err),
+            Errors::ReadFailed { os_error, .. }
+            | Errors::WriteFailed { os_error, .. }
+            | Errors::BrokenPointerPath { os_error, .. }
+            | Errors::AccessDenied { os_error, .. } => {
+                os_error.as_ref().map(|err| err as &(dyn Error + 'static))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Describes a failed memory transfer, e.g.
+/// `"Failed to read 8 bytes at 0x7FF6A000 in process 1234 (0 transferred), OS error 299"`.
+fn transfer_message(
+    operation: &str,
+    pid: Option<u32>,
+    address: usize,
+    requested: usize,
+    transferred: usize,
+    os_error: Option<OsError>,
+) -> String {
+    let mut message = format!("Failed to {operation} {requested} bytes at {address:#X}");
+    push_pid(&mut message, pid);
+    message.push_str(&format!(" ({transferred} transferred)"));
+    push_os_error(&mut message, os_error);
+    message
+}
+
+fn push_pid(message: &mut String, pid: Option<u32>) {
+    if let Some(pid) = pid {
+        message.push_str(&format!(" in process {pid}"));
+    }
+}
+
+fn push_os_error(message: &mut String, os_error: Option<OsError>) {
+    if let Some(os_error) = os_error {
+        message.push_str(&format!(", OS error {}", os_error.code()));
     }
 }
 
@@ -39,7 +328,7 @@ impl Display for Errors<'_> {
 ///
 /// This enables the use of the `?` operator in functions that return [`Errors`]
 /// when calling methods that produce a [`FromBytesUntilNulError`].
-impl From<FromBytesUntilNulError> for Errors<'_> {
+impl From<FromBytesUntilNulError> for Errors {
     /// Converts a [`FromBytesUntilNulError`] into [`Errors::NoNulByte`].
     #[inline]
     fn from(err: FromBytesUntilNulError) -> Self {
@@ -51,7 +340,7 @@ impl From<FromBytesUntilNulError> for Errors<'_> {
 /// This implementation facilitates the propagation of UTF-8 decoding errors
 /// using the `?` operator. It wraps the standard library's [`Utf8Error`] into
 /// the [`Errors::InvalidUtf8`] variant.
-impl From<Utf8Error> for Errors<'_> {
+impl From<Utf8Error> for Errors {
     /// Converts a [`Utf8Error`] into [`Errors::InvalidUtf8`].
     fn from(err: Utf8Error) -> Self {
         Errors::InvalidUtf8(err)
@@ -63,7 +352,7 @@ impl From<Utf8Error> for Errors<'_> {
 /// This implementation enables the use of the `?` operator for functions that return
 /// `Result<T, Errors>` when an integer conversion fails (e.g., due to an overflow
 /// or an out-of-bounds value).
-impl From<TryFromIntError> for Errors<'_> {
+impl From<TryFromIntError> for Errors {
     /// Converts a [`TryFromIntError`] into [`Errors::IntError`].
     fn from(err: TryFromIntError) -> Self {
         Errors::IntError(err)
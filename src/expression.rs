@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+use crate::{errors::Errors, memory::MemoryAccess};
+
+/// A parsed address expression such as `[[client.dll+0x10]+0x18]+4*idx`.
+///
+/// Expressions let configuration files describe addresses without Rust code
+/// changes. They are parsed once and can be evaluated repeatedly against any
+/// [`MemoryAccess`] backend with different [`Environment`]s.
+///
+/// # Syntax
+///
+/// * **Literals**: decimal (`24`) or hexadecimal with a `0x` prefix (`0x18`).
+/// * **Symbols**: variables from the [`Environment`] or module names (`client.dll`),
+///   which evaluate to the module base. Variables take precedence. Names may start
+///   with a digit if they contain a `.` or a character other than a hex digit
+///   (`2dengine.dll`, `3rd`).
+///   Names containing operators must be quoted: `"my-game.exe"`.
+/// * **Operators**: `+`, `-`, `*`, unary `-` and parentheses, with the usual precedence.
+///   Arithmetic wraps around on overflow.
+/// * **Dereference**: `[expr]` reads a pointer at `expr`, as wide as the target's
+///   pointers unless the [`Environment`] overrides it. Prefix the bracket with
+///   `byte`, `word`, `dword` or `qword` to read 1, 2, 4 or 8 bytes instead, e.g.
+///   `dword[client.dll+0x10]`.
+///
+/// Brackets, parentheses and unary minus nest at most [`MAX_NESTING`] levels deep.
+/// Chains of `+`/`-` and `*` do not nest, so they may be of any length.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::expression::{Environment, Expression};
+/// use gamehack_librs::{mock::MockProcess, types::Protection};
+///
+/// let process = MockProcess::new()
+///     .region(0x1000, [0u8; 0x20], Protection::RW)
+///     .module("client.dll", 0x1000, 0x20);
+/// process.poke(0x1010, &0x5000usize.to_ne_bytes());
+///
+/// let expr = Expression::parse("[client.dll+0x10]+4*idx").unwrap();
+/// let env = Environment::new().variable("idx", 3);
+/// assert_eq!(expr.evaluate(&process, &env), Ok(0x500C));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    root: Node,
+}
+
+/// Variables and settings used to evaluate an [`Expression`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Environment {
+    variables: HashMap<String, u64>,
+    pointer_width: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Literal(u64),
+    Symbol(String),
+    Negate(Box<Node>),
+    /// The terms of a flat chain such as `a + b - c`, each with its sign, so long
+    /// chains do not nest.
+    Sum(Vec<(Operator, Node)>),
+    /// The factors of a flat chain such as `a * b * c`.
+    Product(Vec<Node>),
+    Deref(Option<usize>, Box<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Sub,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'src> {
+    Number(u64),
+    Symbol(&'src str),
+    Operator(char),
+}
+
+impl Environment {
+    /// Creates an environment without variables that dereferences pointers of the
+    /// target's [`pointer_width`](MemoryAccess::pointer_width).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines (or redefines) the variable `name`.
+    #[must_use]
+    pub fn variable(mut self, name: &str, value: u64) -> Self {
+        self.variables.insert(name.to_string(), value);
+        self
+    }
+
+    /// Overrides the number of bytes read by an unprefixed `[expr]` dereference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not 1, 2, 4 or 8.
+    #[must_use]
+    pub fn pointer_width(mut self, width: usize) -> Self {
+        assert!(
+            matches!(width, 1 | 2 | 4 | 8),
+            "unsupported pointer width {width}"
+        );
+        self.pointer_width = Some(width);
+        self
+    }
+}
+
+impl Expression {
+    /// Parses an address expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::InvalidExpression`] with the offending part of `expression`
+    /// for malformed literals, unbalanced brackets, unexpected tokens or nesting
+    /// deeper than [`MAX_NESTING`].
+    pub fn parse(expression: &str) -> Result<Self, Errors> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            depth: 0,
+        };
+
+        let root = parser.expression()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(Self { root }),
+            Some(&(_, rest)) => Err(Errors::InvalidExpression(rest.to_string())),
+        }
+    }
+
+    /// Evaluates the expression against `memory`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::UnknownSymbol`] if a name is neither a variable nor a loaded module.
+    /// * [`Errors::ReadFailed`] if a dereferenced address is not readable.
+    pub fn evaluate<M: MemoryAccess + ?Sized>(
+        &self,
+        memory: &M,
+        environment: &Environment,
+    ) -> Result<usize, Errors> {
+        evaluate(&self.root, memory, environment).map(|value| value as usize)
+    }
+}
+
+fn evaluate<M: MemoryAccess + ?Sized>(
+    node: &Node,
+    memory: &M,
+    environment: &Environment,
+) -> Result<u64, Errors> {
+    Ok(match node {
+        Node::Literal(value) => *value,
+        Node::Symbol(name) => match environment.variables.get(name) {
+            Some(&value) => value,
+            None => {
+                memory
+                    .find_module(name)
+                    .ok_or_else(|| Errors::UnknownSymbol(name.to_string()))?
+                    .module_addr as u64
+            }
+        },
+        Node::Negate(inner) => evaluate(inner, memory, environment)?.wrapping_neg(),
+        Node::Sum(terms) => {
+            let mut sum = 0u64;
+            for (operator, term) in terms {
+                let term = evaluate(term, memory, environment)?;
+                sum = match operator {
+                    Operator::Add => sum.wrapping_add(term),
+                    Operator::Sub => sum.wrapping_sub(term),
+                };
+            }
+            sum
+        }
+        Node::Product(factors) => {
+            let mut product = 1u64;
+            for factor in factors {
+                product = product.wrapping_mul(evaluate(factor, memory, environment)?);
+            }
+            product
+        }
+        Node::Deref(width, inner) => {
+            let address = evaluate(inner, memory, environment)? as usize;
+            let width = width
+                .or(environment.pointer_width)
+                .unwrap_or_else(|| memory.pointer_width());
+            let mut bytes = [0u8; 8];
+            memory.read_exact(address, &mut bytes[..width])?;
+            u64::from_le_bytes(bytes)
+        }
+    })
+}
+
+/// Splits `source` into tokens, each paired with the source text it starts at.
+fn tokenize(source: &str) -> Result<Vec<(Token<'_>, &str)>, Errors> {
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = match c {
+            '+' | '-' | '*' | '[' | ']' | '(' | ')' => (Token::Operator(c), 1),
+            '"' => {
+                let len = rest[1..]
+                    .find('"')
+                    .ok_or_else(|| Errors::InvalidExpression(rest.to_string()))?;
+                (Token::Symbol(&rest[1..=len]), len + 2)
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' || c == '.' => {
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                let token = if !c.is_ascii_digit() {
+                    Token::Symbol(word)
+                } else if let Some(value) = parse_number(word) {
+                    Token::Number(value)
+                } else if is_symbol(word) {
+                    Token::Symbol(word)
+                } else {
+                    return Err(Errors::InvalidExpression(word.to_string()));
+                };
+                (token, len)
+            }
+            _ => return Err(Errors::InvalidExpression(rest.to_string())),
+        };
+        tokens.push((token, rest));
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+/// Parses a decimal literal or a hexadecimal one with a `0x` prefix.
+fn parse_number(word: &str) -> Option<u64> {
+    match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => word.parse().ok(),
+    }
+}
+
+/// Returns `true` if `word`, starting with a digit but no number, is a name such as
+/// `2dengine.dll`. Hex digits without `0x` (`1F`) and malformed hex literals
+/// (`0x1G`) are typos rather than names.
+fn is_symbol(word: &str) -> bool {
+    !word.starts_with("0x")
+        && !word.starts_with("0X")
+        && word.contains(|c: char| c == '.' || !c.is_ascii_hexdigit())
+}
+
+/// The deepest nesting of brackets, parentheses and unary minus accepted by
+/// [`Expression::parse`].
+pub const MAX_NESTING: usize = 64;
+
+/// Returns the dereference width selected by a size prefix such as `dword`.
+fn size_prefix(name: &str) -> Option<usize> {
+    match name.to_ascii_lowercase().as_str() {
+        "byte" => Some(1),
+        "word" => Some(2),
+        "dword" => Some(4),
+        "qword" => Some(8),
+        _ => None,
+    }
+}
+
+/// Recursive descent parser over the tokens of an expression.
+struct Parser<'t, 'src> {
+    tokens: &'t [(Token<'src>, &'src str)],
+    position: usize,
+    /// The current nesting of brackets, parentheses and unary minus.
+    depth: usize,
+}
+
+impl<'src> Parser<'_, 'src> {
+    fn peek(&self) -> Option<Token<'src>> {
+        self.tokens.get(self.position).map(|&(token, _)| token)
+    }
+
+    /// Consumes the next token if it is the operator `c`.
+    fn eat(&mut self, c: char) -> bool {
+        let matches = self.peek() == Some(Token::Operator(c));
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    /// Returns the error pointing at the current token, or at the end of the input.
+    fn unexpected(&self) -> Errors {
+        let rest = self.tokens.get(self.position).map_or("", |&(_, rest)| rest);
+        Errors::InvalidExpression(rest.to_string())
+    }
+
+    /// Runs `parse` one nesting level deeper, failing at the current token beyond
+    /// [`MAX_NESTING`] levels instead of overflowing the stack.
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<Node, Errors>,
+    ) -> Result<Node, Errors> {
+        if self.depth == MAX_NESTING {
+            return Err(self.unexpected());
+        }
+        self.depth += 1;
+        let node = parse(self);
+        self.depth -= 1;
+        node
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Errors> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// `expression := term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<Node, Errors> {
+        let mut terms = vec![(Operator::Add, self.term()?)];
+        loop {
+            let operator = if self.eat('+') {
+                Operator::Add
+            } else if self.eat('-') {
+                Operator::Sub
+            } else {
+                break;
+            };
+            terms.push((operator, self.term()?));
+        }
+        Ok(match terms.len() {
+            1 => terms.pop().expect("one term").1,
+            _ => Node::Sum(terms),
+        })
+    }
+
+    /// `term := unary ('*' unary)*`
+    fn term(&mut self) -> Result<Node, Errors> {
+        let mut factors = vec![self.unary()?];
+        while self.eat('*') {
+            factors.push(self.unary()?);
+        }
+        Ok(match factors.len() {
+            1 => factors.pop().expect("one factor"),
+            _ => Node::Product(factors),
+        })
+    }
+
+    /// `unary := '-' unary | primary`
+    fn unary(&mut self) -> Result<Node, Errors> {
+        if self.eat('-') {
+            self.nested(|parser| Ok(Node::Negate(Box::new(parser.unary()?))))
+        } else {
+            self.primary()
+        }
+    }
+
+    /// `primary := number | symbol | '(' expression ')' | [size] '[' expression ']'`
+    fn primary(&mut self) -> Result<Node, Errors> {
+        let node = match self.peek() {
+            Some(Token::Number(value)) => {
+                self.position += 1;
+                Node::Literal(value)
+            }
+            Some(Token::Symbol(name)) => {
+                self.position += 1;
+                match size_prefix(name) {
+                    Some(width) if self.eat('[') => self.dereference(Some(width))?,
+                    _ => Node::Symbol(name.to_string()),
+                }
+            }
+            Some(Token::Operator('(')) => {
+                self.position += 1;
+                self.nested(|parser| {
+                    let node = parser.expression()?;
+                    parser.expect(')')?;
+                    Ok(node)
+                })?
+            }
+            Some(Token::Operator('[')) => {
+                self.position += 1;
+                self.dereference(None)?
+            }
+            _ => return Err(self.unexpected()),
+        };
+        Ok(node)
+    }
+
+    /// Parses the inside of a dereference after its opening bracket.
+    fn dereference(&mut self, width: Option<usize>) -> Result<Node, Errors> {
+        self.nested(|parser| {
+            let inner = parser.expression()?;
+            parser.expect(']')?;
+            Ok(Node::Deref(width, Box::new(inner)))
+        })
+    }
+}
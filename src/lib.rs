@@ -1,241 +1,466 @@
+// Lets the code generated by the derive macros refer to `::gamehack_librs` here too
+extern crate self as gamehack_librs;
+
+pub mod batch;
+pub mod enumeration;
 mod errors;
+pub mod expression;
+#[cfg(target_os = "linux")]
+pub mod linux;
+pub mod memory;
+pub mod mock;
+pub mod options;
+pub mod pattern;
+pub mod pod;
+pub mod pointer;
+pub mod process;
+pub mod remote;
+pub mod strings;
+#[cfg(test)]
 mod tests;
 pub mod types;
 pub mod utils;
+#[cfg(windows)]
+pub mod win32;
 
-use std::ptr::{self, addr_of, addr_of_mut};
-
-use windows::{
-    Win32::{
-        Foundation::{CloseHandle, HANDLE, HMODULE},
-        System::{
-            Diagnostics::Debug::{ReadProcessMemory, WriteProcessMemory},
-            ProcessStatus::{EnumProcesses, GetModuleBaseNameA},
-            Threading::{OpenProcess, PROCESS_ALL_ACCESS, PROCESS_QUERY_INFORMATION},
-        },
-    },
-    core::Error,
+use std::{
+    mem::MaybeUninit,
+    ops::Range,
+    ptr, slice,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
 };
 
-use errors::Errors;
-use types::{ProcessData, TransformName};
-use utils::process_modules;
+#[cfg(target_os = "linux")]
+use linux as platform;
+#[cfg(windows)]
+use win32 as platform;
+
+pub use enumeration::{ProcessEntry, Processes};
+pub use errors::{Errors, OsError};
+pub use gamehack_librs_derive::{Pod, RemoteStruct};
+pub use memory::MemoryAccess;
+use memory::PAGE_SIZE;
+pub use options::OpenOptions;
+pub use pattern::Pattern;
+pub use pod::Pod;
+pub use pointer::{PointerPath, RemotePtr};
+pub use process::Process;
+pub use remote::RemoteStruct;
+#[cfg(windows)]
+pub use win32::{close_handle, get_process_handle};
 
-/// Opens a local process and returns a handle with full access rights.
+/// Searches for a process by its name and opens it.
 ///
-/// This function wraps the Win32 [`OpenProcess`] call. It is used to obtain a
-/// handle that allows for extensive operations, including reading/writing memory
-/// and querying process information.
+/// This function enumerates all active processes on the system, compares their
+/// names (case-insensitive) with the provided `process_name`, and returns a
+/// [`Process`] for the first matching instance that can be opened. Use
+/// [`find_processes`] to choose between several instances.
 ///
 /// # Arguments
 ///
-/// * `pid` - The unique process identifier (PID) of the target process.
+/// * `process_name` - A string slice containing the name of the executable
+///   (e.g., "discord.exe").
 ///
 /// # Returns
 ///
-/// * `Ok(HANDLE)` - A valid, open handle to the process if successful.
-/// * `Err(Error)` - An error indicating failure, such as if the process does not exist
-///   or the current user lacks sufficient privileges (e.g., `ERROR_ACCESS_DENIED`).
-///
-/// # Security Warning
-///
-/// This function requests **`PROCESS_ALL_ACCESS`**. In modern Windows environments (2026),
-/// this may require the calling process to have `SeDebugPrivilege` enabled or to
-/// be running with Administrative privileges. Excessive permissions may
-/// also trigger Attack Surface Reduction (ASR) rules or EDR alerts.
-///
-/// # Safety
+/// * `Ok(Process)` - Owns the handle of the found process and its module list.
+/// * `Err(Errors::ProcessNotFound)` - Returned if no process matches the name
+///   or if no matching process could be opened.
 ///
-/// This function uses an `unsafe` block to call a foreign API. It is considered
-/// a safe wrapper because:
-/// 1. It validates the return value of `OpenProcess`.
-/// 2. It converts the null-handle failure state into a standard Rust [`Result`].
+/// # Technical Details
 ///
-/// **Note:** The caller is responsible for eventually closing the returned handle
-/// using [`close_handle`] to prevent resource leaks.
-pub fn get_process_handle(pid: u32) -> Result<HANDLE, Error> {
-    unsafe { OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_ALL_ACCESS, false, pid) }
+/// 1. **Enumeration**: Walks [`processes`], which opens no handles.
+/// 2. **Comparison**: Performs a case-insensitive match against the executable name.
+/// 3. **Opening**: Only matching processes are opened, skipping those that cannot be.
+/// 4. **Deep Scan**: [`Process::open`] populates the module information.
+pub fn find_process(process_name: &str) -> Result<Process, Errors> {
+    processes()
+        .filter(|entry| entry.name().eq_ignore_ascii_case(process_name))
+        .find_map(|entry| entry.open().ok())
+        .ok_or(Errors::ProcessNotFound)
+}
+
+/// Returns every running process whose executable name matches `process_name`
+/// (case-insensitive), e.g. all instances of a multi-boxed game client.
+///
+/// No process is opened; call [`ProcessEntry::open`] on the chosen entry.
+#[must_use]
+pub fn find_processes(process_name: &str) -> Vec<ProcessEntry> {
+    processes()
+        .filter(|entry| entry.name().eq_ignore_ascii_case(process_name))
+        .collect()
 }
 
-/// Closes an open object handle.
+/// How long [`wait_for_process`] sleeps between two scans of the process list.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Waits until a process named `process_name` is running and opens it.
 ///
-/// This is a safe wrapper around the Win32 [`CloseHandle`] function. It ensures
-/// that system resources associated with the handle are released.
+/// Intended for tools that are started before the game. The process list is
+/// polled every 100 ms; a process only counts as started once its main module
+/// (the module named `process_name`) is loaded, so the returned [`Process`] is
+/// ready to use. Pass [`Duration::MAX`] to wait forever.
 ///
-/// # Arguments
+/// # Errors
 ///
-/// * `handle` - A valid [`HANDLE`] to an open object (e.g., process, thread, or file).
+/// * [`Errors::Timeout`] if no matching process was started in time.
+/// * The error of [`OpenOptions::open`], e.g. [`Errors::AccessDenied`], as soon as a
+///   matching process cannot be opened for a reason other than having exited.
 ///
-/// # Side Effects
+/// # Example
 ///
-/// Closing a handle invalidates the handle value, making it unusable for further calls.
-/// Note that for some objects, like threads or processes, closing the handle does not
-/// terminate the object; it only removes your access to it.
+/// ```no_run
+/// use std::time::Duration;
 ///
-/// # Safety
-///
-/// While this function is marked as `pub`, it wraps an `unsafe` block. It assumes
-/// that the provided `handle` is either a valid open handle or `NULL`.
-/// Passing a pseudo-handle or an already closed handle may lead to undefined
-/// behavior in some Windows environments, although `CloseHandle` usually
-/// just returns an error.
-pub fn close_handle(handle: HANDLE) {
-    unsafe {
-        // We ignore the return value (BOOL) as there is often little
-        // recovery logic possible if a handle fails to close.
-        let _ = CloseHandle(handle);
-    }
+/// let game = gamehack_librs::wait_for_process("hitman3.exe", Duration::from_secs(60)).unwrap();
+/// ```
+pub fn wait_for_process(process_name: &str, timeout: Duration) -> Result<Process, Errors> {
+    wait_for_process_cancellable(process_name, timeout, &AtomicBool::new(false))
 }
 
-/// Searches for a process by its name and retrieves its system data.
+/// Cancellable variant of [`wait_for_process`] for UI tools.
 ///
-/// This function enumerates all active processes on the system, compares their
-/// names (case-insensitive) with the provided `process_name`, and populates
-/// a [`ProcessData`] struct for the first matching instance.
+/// Setting `cancel` to `true` from another thread stops waiting at the next poll.
 ///
-/// # Arguments
+/// # Errors
 ///
-/// * `process_name` - A string slice containing the name of the executable
-///   (e.g., "discord.exe").
-///
-/// # Returns
-///
-/// * `Ok(ProcessData<String>)` - Contains the handle, PID, and module list
-///   of the found process.
-/// * `Err(Errors::ProcessNotFound)` - Returned if no process matches the name
-///   or if the matching process could not be opened.
-///
-/// # Technical Details
-///
-/// 1. **Enumeration**: Uses `EnumProcesses` with a static buffer limit of 1024 PIDs.
-/// 2. **Filtering**: Automatically skips PIDs that cannot be opened with
-///    `PROCESS_ALL_ACCESS` (via [`get_process_handle`]).
-/// 3. **Comparison**: Performs a case-insensitive match against the base module name.
-/// 4. **Deep Scan**: If a match is found, [`process_modules`] is called to
-///    populate additional module information.
-///
-/// # Safety
-///
-/// While the function is safe to call, it internally handles raw pointers and
-/// Win32 API calls. It relies on [`get_process_handle`] and ensures handles are
-/// managed within the [`ProcessData`] context.
-pub fn find_process(process_name: &str) -> Result<ProcessData<String>, Errors<'_>> {
-    let mut pid_list = [0u32; 1024];
-    let mut cb_needed = 0;
-    let mut process_data = ProcessData::default();
-
-    unsafe {
-        let _ = EnumProcesses(
-            pid_list.as_mut_ptr().cast(),
-            u32::try_from(size_of_val(&pid_list))?,
-            addr_of_mut!(cb_needed),
-        );
-    }
-
-    let limit = cb_needed as usize / size_of::<u32>();
+/// * [`Errors::Cancelled`] once `cancel` is set.
+/// * [`Errors::Timeout`] or the error of opening a matching process, as for
+///   [`wait_for_process`].
+pub fn wait_for_process_cancellable(
+    process_name: &str,
+    timeout: Duration,
+    cancel: &AtomicBool,
+) -> Result<Process, Errors> {
+    let deadline = Instant::now().checked_add(timeout);
 
-    for (pid, handle) in pid_list
-        .iter()
-        .take(limit)
-        .filter(|&&pid| pid != 0)
-        .filter_map(|&pid| get_process_handle(pid).ok().map(|h| (pid, h)))
-    {
-        let hmod = HMODULE::default();
-        let mut module_name = [0u8; 256];
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(Errors::Cancelled);
+        }
 
-        unsafe {
-            let _ = GetModuleBaseNameA(handle, Some(hmod), &mut module_name);
+        for entry in find_processes(process_name) {
+            match entry.open() {
+                Ok(process) if process.module(process_name).is_some() => return Ok(process),
+                // Still loading, or exited since the process list was taken
+                Ok(_) | Err(Errors::ProcessNotFound) => {}
+                Err(err) => return Err(err),
+            }
         }
 
-        if module_name
-            .to_string_lowercase()
-            .unwrap_or("<Module Name>".to_string())
-            == process_name.to_ascii_lowercase()
-        {
-            process_data.handle = handle;
-            process_data.id = pid;
-            process_modules(&mut process_data);
+        let remaining = match deadline {
+            Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+            None => POLL_INTERVAL,
+        };
+        if remaining.is_zero() {
+            return Err(Errors::Timeout);
         }
+        thread::sleep(remaining.min(POLL_INTERVAL));
     }
+}
 
-    if process_data.id == 0 {
-        Err(Errors::ProcessNotFound)
-    } else {
-        Ok(process_data)
-    }
+/// Enumerates the running processes without opening them.
+///
+/// # Example
+///
+/// ```
+/// for entry in gamehack_librs::processes() {
+///     println!("{:>6} {:>6} {}", entry.pid(), entry.parent_pid(), entry.name());
+/// }
+/// ```
+#[must_use]
+pub fn processes() -> Processes {
+    Processes::new()
 }
 
 /// Performs a multi-level pointer traversal and reads the final value into a buffer.
 ///
 /// This function follows a chain of pointers starting from a base `addr`,
-/// applying a sequence of `offsets`, and finally writing the resulting address
-/// (or value) into the provided `buffer`.
+/// applying a sequence of `offsets`, and finally writing the value found at the
+/// resolved address into the provided `buffer`. It is the infallible counterpart
+/// of [`read_chain`].
 ///
 /// # Arguments
 ///
-/// * `handle` - A valid [`HANDLE`] to the target process with `PROCESS_VM_READ` access.
+/// * `memory` - Any [`MemoryAccess`] backend of the target process with read access.
 /// * `addr` - The initial base address to start the pointer chain.
 /// * `offsets` - A slice of [`u32`] offsets to be applied sequentially during traversal.
-/// * `buffer` - A raw pointer to a location of type `T` where the final address will be written.
+/// * `buffer` - The value of type `T` that receives the final value.
 ///
 /// # Traversal Logic
 ///
-/// 1. Reads a `usize` from `addr` into an internal temporary address.
-/// 2. For each `offset` in `offsets`:
-///    - Adds the offset to the temporary address (using wrapping addition).
-///    - Reads the next `usize` from that location.
-/// 3. Finally, writes the last resolved address into `buffer`.
-///
-/// # Safety
-///
-/// This function is **high-risk** and marked `pub` despite containing an `unsafe` block:
-/// * **Pointer Dereferencing**: It assumes that every step in the chain results in a readable memory location. If any pointer in the chain is invalid, `ReadProcessMemory` will fail, and the function will continue with stale data.
-/// * **Buffer Validity**: The caller must ensure that `buffer` points to valid, initialized memory capable of holding a value of type `T`.
-/// * **Type Size**: Note that this function specifically reads `size_of::<usize>()` at each step, regardless of the size of `T`.
-///
-pub fn read<T: Copy + Sized>(handle: HANDLE, addr: usize, offsets: &[u32], buffer: *mut T) {
-    let size = size_of::<usize>();
-    let mut next_addr = 0usize;
-
-    unsafe {
-        let _ = ReadProcessMemory(
-            handle,
-            addr as *const _,
-            addr_of_mut!(next_addr).cast(),
-            size,
-            None,
-        );
-
-        for &offset in offsets {
-            let _ = ReadProcessMemory(
-                handle,
-                (next_addr.wrapping_add(offset as usize)) as *const _,
-                addr_of_mut!(next_addr).cast(),
-                size,
-                None,
-            );
+/// 1. Resolves the chain with [`resolve_chain`]: for each `offset` in `offsets`, a
+///    pointer of the target's width is read from the current address and the offset
+///    is added to it.
+/// 2. Reads exactly `size_of::<T>()` bytes from the resolved address.
+/// 3. Finally, writes the value into `buffer`.
+///
+/// If any pointer in the chain is invalid, the read fails and `buffer` is left
+/// untouched. Use [`try_read`] or [`read_chain`] to find out why.
+pub fn read<M, T>(memory: &M, addr: usize, offsets: &[u32], buffer: &mut T)
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    let _ = try_read(memory, addr, offsets, buffer);
+}
+
+/// Fallible variant of [`read`] that stops at the first unreadable hop.
+///
+/// The traversal is identical to [`read`], but every memory access must copy the
+/// full value. Instead of continuing the chain with stale data, the first failing
+/// access is reported and `buffer` is left untouched.
+///
+/// # Errors
+///
+/// Returns [`Errors::ReadFailed`] with the address of the failing hop, the number
+/// of bytes transferred before the failure (partial copy) and the OS error.
+pub fn try_read<M, T>(
+    memory: &M,
+    addr: usize,
+    offsets: &[u32],
+    buffer: &mut T,
+) -> Result<(), Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    *buffer = read_chain(memory, addr, offsets)?;
+    Ok(())
+}
+
+/// Reads a value of type `T` located at `addr`.
+///
+/// Exactly `size_of::<T>()` bytes are copied, so reading a `u32` never touches
+/// the memory after it, neither in the target nor in the current process.
+///
+/// # Errors
+///
+/// Returns [`Errors::ReadFailed`] if the value could not be read completely.
+pub fn read_value<M, T>(memory: &M, addr: usize) -> Result<T, Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    let mut value = MaybeUninit::<T>::zeroed();
+    let bytes =
+        unsafe { slice::from_raw_parts_mut(value.as_mut_ptr().cast::<u8>(), size_of::<T>()) };
+
+    memory.read_exact(addr, bytes)?;
+    Ok(unsafe { value.assume_init() })
+}
+
+/// Reads `count` consecutive values of type `T` starting at `addr`, e.g. an entity
+/// array.
+///
+/// The whole array is copied with a single read. Use [`read_into`] to reuse a buffer
+/// or to keep the readable elements when some of them are not.
+///
+/// `count` often comes from the target itself, so it is checked before the array is
+/// allocated: arrays over 1 MiB must fit into the memory mapped at `addr`, as far as
+/// [`MemoryAccess::regions`] lists it.
+///
+/// # Errors
+///
+/// * [`Errors::SliceTooLarge`] if the array overflows the address space or exceeds
+///   the mapped memory.
+/// * [`Errors::UnreadableElements`] with the indices of the elements that lie on
+///   unreadable pages, if the others could be read.
+/// * [`Errors::ReadFailed`] if no element could be read at all.
+pub fn read_slice<M, T>(memory: &M, addr: usize, count: usize) -> Result<Vec<T>, Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    let too_large = || Errors::SliceTooLarge {
+        address: addr,
+        count,
+        element_size: size_of::<T>(),
+    };
+    let len = count
+        .checked_mul(size_of::<T>())
+        .filter(|&len| addr.checked_add(len).is_some())
+        .ok_or_else(too_large)?;
+    if len > UNCHECKED_SLICE_LEN && mapped_len(memory, addr).is_some_and(|mapped| mapped < len) {
+        return Err(too_large());
+    }
+
+    let mut values = vec![MaybeUninit::<T>::zeroed(); count];
+    let bytes = unsafe {
+        slice::from_raw_parts_mut(values.as_mut_ptr().cast::<u8>(), size_of_val(&*values))
+    };
+
+    read_elements(memory, addr, bytes, size_of::<T>())?;
+    Ok(values
+        .into_iter()
+        .map(|value| unsafe { value.assume_init() })
+        .collect())
+}
+
+/// Arrays up to this many bytes are allocated without listing the regions of the
+/// target, which would cost more than the allocation.
+const UNCHECKED_SLICE_LEN: usize = 0x10_0000;
+
+/// Returns how many bytes from `addr` on are mapped in consecutive regions, or
+/// `None` if `memory` lists no regions at all.
+fn mapped_len<M: MemoryAccess + ?Sized>(memory: &M, addr: usize) -> Option<usize> {
+    let mut regions = memory.regions();
+    if regions.is_empty() {
+        return None;
+    }
+    regions.sort_unstable_by_key(|region| region.base);
+
+    let mut end = addr;
+    for region in regions {
+        if region.base <= end && end < region.end() {
+            end = region.end();
+        }
+    }
+    Some(end - addr)
+}
+
+/// Fills `values` with consecutive values of type `T` starting at `addr`.
+///
+/// The whole slice is copied with a single read. If that fails, the range is read
+/// again page by page, so every element on a readable page is still filled in and
+/// only the others are reported.
+///
+/// # Errors
+///
+/// * [`Errors::UnreadableElements`] with the indices of the elements that lie on
+///   unreadable pages, if the others could be read. Those elements are zeroed, all
+///   other elements hold the values read.
+/// * [`Errors::ReadFailed`] if no element could be read at all. The contents of
+///   `values` are unspecified in that case.
+pub fn read_into<M, T>(memory: &M, addr: usize, values: &mut [T]) -> Result<(), Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    let bytes =
+        unsafe { slice::from_raw_parts_mut(values.as_mut_ptr().cast::<u8>(), size_of_val(values)) };
+    read_elements(memory, addr, bytes, size_of::<T>())
+}
+
+/// Reads `bytes` holding elements of `element_size` bytes in one go, falling back
+/// to one read per page to find out which elements are unreadable.
+fn read_elements<M>(
+    memory: &M,
+    addr: usize,
+    bytes: &mut [u8],
+    element_size: usize,
+) -> Result<(), Errors>
+where
+    M: MemoryAccess + ?Sized,
+{
+    let Err(err) = memory.read_exact(addr, bytes) else {
+        return Ok(());
+    };
+
+    let mut unreadable: Vec<Range<usize>> = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let current = addr.wrapping_add(offset);
+        let chunk = (PAGE_SIZE - current % PAGE_SIZE).min(bytes.len() - offset);
+
+        if memory
+            .read_exact(current, &mut bytes[offset..offset + chunk])
+            .is_err()
+        {
+            // Elements straddling the page boundary are unreadable as well
+            let elements = offset / element_size..(offset + chunk).div_ceil(element_size);
+            match unreadable.last_mut() {
+                Some(last) if last.end >= elements.start => last.end = elements.end,
+                _ => unreadable.push(elements),
+            }
+        }
+        offset += chunk;
+    }
+
+    match unreadable.as_slice() {
+        [] => Ok(()),
+        [all] if all.len() * element_size == bytes.len() => Err(err),
+        _ => {
+            for elements in &unreadable {
+                bytes[elements.start * element_size..elements.end * element_size].fill(0);
+            }
+            Err(Errors::UnreadableElements {
+                address: addr,
+                unreadable,
+            })
         }
-        ptr::write(buffer.cast(), next_addr);
     }
 }
 
+/// Reads a pointer of the target's [`pointer_width`](MemoryAccess::pointer_width)
+/// at `addr`, so 4-byte pointers of 32-bit targets are zero-extended to `usize`.
+///
+/// # Errors
+///
+/// Returns [`Errors::ReadFailed`] if the pointer could not be read completely.
+pub fn read_pointer<M>(memory: &M, addr: usize) -> Result<usize, Errors>
+where
+    M: MemoryAccess + ?Sized,
+{
+    let mut bytes = [0u8; 8];
+    memory.read_exact(addr, &mut bytes[..memory.pointer_width()])?;
+    Ok(u64::from_le_bytes(bytes) as usize)
+}
+
+/// Resolves a pointer chain and returns the final address without dereferencing it.
+///
+/// Starting at `base`, each offset dereferences the current address as a pointer of
+/// the target's width (see [`read_pointer`]) and adds the offset:
+/// `resolve_chain(base, &[0x10, 0x18])` is `[[base] + 0x10] + 0x18` in Cheat
+/// Engine notation. An empty `offsets` slice returns `base` unchanged.
+///
+/// # Errors
+///
+/// Returns [`Errors::ReadFailed`] with the address of the first unreadable hop.
+pub fn resolve_chain<M>(memory: &M, base: usize, offsets: &[u32]) -> Result<usize, Errors>
+where
+    M: MemoryAccess + ?Sized,
+{
+    offsets.iter().try_fold(base, |addr, &offset| {
+        read_pointer(memory, addr).map(|next| next.wrapping_add(offset as usize))
+    })
+}
+
+/// Resolves a pointer chain with [`resolve_chain`] and reads the final hop as `T`.
+///
+/// # Errors
+///
+/// Returns [`Errors::ReadFailed`] with the address of the first unreadable hop,
+/// including the final value.
+pub fn read_chain<M, T>(memory: &M, base: usize, offsets: &[u32]) -> Result<T, Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    read_value(memory, resolve_chain(memory, base, offsets)?)
+}
+
 /// Writes a value of type `T` to a specific memory address in the target process.
 ///
-/// This function is a high-level wrapper around the Win32 [`WriteProcessMemory`] API.
-/// It uses generics to allow writing any type that implements [`Copy`].
+/// This function is a high-level wrapper around [`MemoryAccess::write_bytes`]
+/// (`WriteProcessMemory` on Windows, `process_vm_writev` on Linux).
+/// It uses generics to allow writing any plain-old-data type that implements [`Pod`].
 ///
 /// # Arguments
 ///
-/// * `handle` - A valid [`HANDLE`] to the target process with `PROCESS_VM_WRITE`
-///   and `PROCESS_VM_OPERATION` access rights.
+/// * `memory` - Any [`MemoryAccess`] backend of the target process with write access
+///   (`PROCESS_VM_WRITE` and `PROCESS_VM_OPERATION` on Windows).
 /// * `addr` - The base address in the specified process to which data is written.
 /// * `value` - A reference to the value of type `T` to be written to the target process.
 ///
 /// # Type Constraints
 ///
-/// * `T: Copy` - Ensures that the type can be safely copied bitwise. This prevents
-///   passing types with complex ownership (like `String` or `Vec`), which would
-///   result in writing pointers that are invalid in the target process's address space.
+/// * `T: Pod` - Ensures that the type can be safely copied bitwise. This prevents
+///   passing types with complex ownership (like `String` or `Vec`), references and
+///   raw pointers, which would result in writing pointers that are invalid in the
+///   target process's address space.
 ///
 /// # Safety and Side Effects
 ///
@@ -245,14 +470,27 @@ pub fn read<T: Copy + Sized>(handle: HANDLE, addr: usize, offsets: &[u32], buffe
 ///   will fail silently (as the result is currently ignored).
 /// * **Pointer Validity**: The caller must ensure that `addr` is valid within
 ///   the context of the target process, not the current one.
-pub fn write<T: Copy + Sized>(handle: HANDLE, addr: usize, value: &T) {
-    unsafe {
-        let _ = WriteProcessMemory(
-            handle,
-            addr as *const _,
-            addr_of!(value).cast(),
-            size_of::<T>(),
-            None,
-        );
-    }
+pub fn write<M, T>(memory: &M, addr: usize, value: &T)
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    let bytes = unsafe { slice::from_raw_parts(ptr::from_ref(value).cast::<u8>(), size_of::<T>()) };
+    let _ = memory.write_bytes(addr, bytes);
+}
+
+/// Fallible variant of [`write`].
+///
+/// # Errors
+///
+/// Returns [`Errors::WriteFailed`] with `addr`, the number of bytes transferred
+/// before the failure (partial copy) and the OS error, e.g. when the target
+/// page is read-only.
+pub fn try_write<M, T>(memory: &M, addr: usize, value: &T) -> Result<(), Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: Pod,
+{
+    let bytes = unsafe { slice::from_raw_parts(ptr::from_ref(value).cast::<u8>(), size_of::<T>()) };
+    memory.write_all(addr, bytes)
 }
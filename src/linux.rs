@@ -0,0 +1,491 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, ReadDir},
+    io::{self, Read},
+    os::fd::RawFd,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use libc::{UIO_MAXIOV, iovec, pid_t, process_vm_readv, process_vm_writev};
+
+use crate::{
+    enumeration::ProcessEntry,
+    errors::{Errors, OsError},
+    memory::MemoryAccess,
+    options::{Access, OpenOptions},
+    types::{Architecture, MemoryRegion, ModuleData, Protection},
+};
+
+/// The process reference used by the Linux backend.
+///
+/// Linux has no process handles to open or close: every operation addresses the
+/// target by its PID through `process_vm_readv`/`process_vm_writev` and the
+/// `/proc/<pid>` pseudo-filesystem. Access is governed by the ptrace access mode
+/// checks (same user and a permissive `kernel.yama.ptrace_scope`, or `CAP_SYS_PTRACE`).
+///
+/// A handle obtained through [`OpenOptions::open`] remembers the start time of the
+/// process, so a new process that reuses the PID is not mistaken for the target.
+/// On kernels with `pidfd_open` (5.3 and later) it also holds a pidfd, which turns
+/// readable once the target exits and tells cheaply whether an operation that
+/// succeeded still reached it. The [`Process`](crate::Process) that owns the handle
+/// closes the pidfd.
+///
+/// Every handle reads the architecture of the target once, from the ELF header of
+/// its executable, so following pointers does not read it again on every hop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawHandle {
+    pid: pid_t,
+    start_time: Option<u64>,
+    pidfd: Option<RawFd>,
+    architecture: Option<Architecture>,
+}
+
+impl RawHandle {
+    /// Wraps `pid` without checking that the process exists.
+    #[must_use]
+    pub fn new(pid: u32) -> Self {
+        Self {
+            architecture: process_architecture(pid),
+            ..Self::bare(pid)
+        }
+    }
+
+    /// Wraps `pid` for looking up entries of `/proc/<pid>`, without reading the
+    /// architecture.
+    fn bare(pid: u32) -> Self {
+        Self {
+            pid: pid.cast_signed(),
+            start_time: None,
+            pidfd: None,
+            architecture: None,
+        }
+    }
+
+    /// Returns the PID this handle refers to.
+    #[must_use]
+    pub fn pid(&self) -> u32 {
+        self.pid.cast_unsigned()
+    }
+
+    /// Returns the path of an entry in the `/proc/<pid>` directory of the target.
+    fn proc_path(&self, entry: &str) -> String {
+        format!("/proc/{}/{entry}", self.pid)
+    }
+}
+
+/// Checks that the process with the given `pid` exists and that the requested
+/// rights pass the ptrace access mode checks, for [`OpenOptions::open`].
+///
+/// Reading and writing are probed by opening `/proc/<pid>/mem`, querying by opening
+/// `/proc/<pid>/maps`; the kernel applies the same checks as to `process_vm_readv`.
+pub(crate) fn open_process(pid: u32, options: &OpenOptions) -> Result<RawHandle, Errors> {
+    let mut handle = RawHandle::bare(pid);
+    let stat = read_stat(handle).ok_or(Errors::ProcessNotFound)?;
+    handle.start_time = start_time_of(&stat);
+
+    for access in Access::ALL
+        .into_iter()
+        .filter(|&access| options.allows(access))
+    {
+        let (entry, write) = match access {
+            Access::Read => ("mem", false),
+            Access::Write => ("mem", true),
+            Access::Query => ("maps", false),
+            Access::Operation | Access::Synchronize => continue,
+        };
+        fs::OpenOptions::new()
+            .read(!write)
+            .write(write)
+            .open(handle.proc_path(entry))
+            .map_err(|err| Errors::AccessDenied {
+                pid,
+                access: Some(access),
+                os_error: OsError::of(&err),
+            })?;
+    }
+
+    // The pidfd refers to whichever process holds the PID by now, which has to be
+    // the one that was probed
+    handle.pidfd = pidfd_open(handle.pid);
+    if read_stat(handle).and_then(|stat| start_time_of(&stat)) != handle.start_time {
+        close_pidfd(handle);
+        return Err(Errors::ProcessNotFound);
+    }
+    handle.architecture = process_architecture(pid);
+    Ok(handle)
+}
+
+/// Opens a pidfd for `pid`, or returns `None` if the kernel does not support them.
+fn pidfd_open(pid: pid_t) -> Option<RawFd> {
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+    RawFd::try_from(fd).ok().filter(|&fd| fd >= 0)
+}
+
+/// Checks whether `pidfd` is readable, which it becomes once its process exits.
+fn pidfd_exited(pidfd: RawFd, timeout_ms: i32) -> bool {
+    let mut pollfd = libc::pollfd {
+        fd: pidfd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut pollfd, 1, timeout_ms) > 0 }
+}
+
+/// Closes the pidfd of `handle`, if it holds one.
+pub(crate) fn close_pidfd(handle: RawHandle) {
+    if let Some(pidfd) = handle.pidfd {
+        unsafe { libc::close(pidfd) };
+    }
+}
+
+/// Lazily walks the numeric entries of `/proc`.
+pub(crate) struct ProcessIter {
+    entries: Option<ReadDir>,
+}
+
+/// Starts enumerating processes for [`processes`](crate::processes).
+pub(crate) fn processes() -> ProcessIter {
+    ProcessIter {
+        entries: fs::read_dir("/proc").ok(),
+    }
+}
+
+impl Iterator for ProcessIter {
+    type Item = ProcessEntry;
+
+    fn next(&mut self) -> Option<ProcessEntry> {
+        self.entries.as_mut()?.find_map(|entry| {
+            let pid = entry.ok()?.file_name().to_str()?.parse::<u32>().ok()?;
+            let handle = RawHandle::bare(pid);
+            // A process that exited since `/proc` was listed has no `stat` anymore
+            let parent_pid = parent_pid_of(handle)?;
+            Some(ProcessEntry::new(pid, parent_pid, process_name_of(handle)?))
+        })
+    }
+}
+
+/// Reads `/proc/<pid>/stat`, which disappears once the process has been reaped.
+fn read_stat(handle: RawHandle) -> Option<String> {
+    fs::read_to_string(handle.proc_path("stat")).ok()
+}
+
+/// Returns the start time of the process, field 22 of a `stat` line.
+fn start_time_of(stat: &str) -> Option<u64> {
+    stat_field(stat, 22)?.parse().ok()
+}
+
+/// Returns field `number` (1-based, as numbered in `proc(5)`) of a `stat` line.
+///
+/// The second field is the parenthesized `comm`, which may contain spaces, so
+/// fields from the third on are split after its closing parenthesis.
+fn stat_field(stat: &str, number: usize) -> Option<&str> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(number.checked_sub(3)?)
+}
+
+/// Reads the parent PID, the fourth field of `/proc/<pid>/stat`.
+fn parent_pid_of(handle: RawHandle) -> Option<u32> {
+    stat_field(&read_stat(handle)?, 4)?.parse().ok()
+}
+
+/// Checks whether the process behind `handle` has exited.
+///
+/// Returns `Some` once the process is gone, is a zombie, or its PID has been reused,
+/// holding the exit code if it exited normally and the kernel reports it (field 52
+/// of `stat`, which needs ptrace access). Returns `None` while it is running.
+pub(crate) fn exit_code(handle: &RawHandle) -> Option<Option<i32>> {
+    // The PID cannot have been reused before the target exited
+    if handle.pidfd.is_some_and(|pidfd| !pidfd_exited(pidfd, 0)) {
+        return None;
+    }
+    let Some(stat) = read_stat(*handle) else {
+        return Some(None);
+    };
+
+    if handle.start_time.is_some() && start_time_of(&stat) != handle.start_time {
+        return Some(None);
+    }
+    if !matches!(stat_field(&stat, 3), Some("Z" | "X")) {
+        return None;
+    }
+
+    let status = stat_field(&stat, 52).and_then(|status| status.parse::<i32>().ok());
+    Some(
+        status
+            .filter(|&status| libc::WIFEXITED(status))
+            .map(|status| libc::WEXITSTATUS(status)),
+    )
+}
+
+/// Returns a blocking function that waits for the process behind `handle` to exit.
+///
+/// The waiting thread blocks on a duplicate of the pidfd, which it closes when done,
+/// and falls back to polling `/proc` every 100 ms without one.
+pub(crate) fn exit_waiter(
+    handle: &RawHandle,
+    _pid: u32,
+) -> Result<impl FnOnce() -> Option<i32> + Send + 'static, Errors> {
+    let mut handle = *handle;
+    handle.pidfd = handle.pidfd.and_then(|pidfd| {
+        let duplicate = unsafe { libc::fcntl(pidfd, libc::F_DUPFD_CLOEXEC, 0) };
+        (duplicate >= 0).then_some(duplicate)
+    });
+    Ok(move || {
+        if let Some(pidfd) = handle.pidfd {
+            while !pidfd_exited(pidfd, -1) {}
+        }
+        let code = loop {
+            if let Some(code) = exit_code(&handle) {
+                break code;
+            }
+            thread::sleep(Duration::from_millis(100));
+        };
+        close_pidfd(handle);
+        code
+    })
+}
+
+/// Checks, after an operation succeeded, whether the target had already exited and
+/// the operation may have reached a new process that reuses its PID.
+///
+/// Costs a single `poll` with a pidfd and a read of `/proc/<pid>/stat` without one.
+pub(crate) fn is_stale(handle: &RawHandle) -> bool {
+    match handle.pidfd {
+        Some(pidfd) => pidfd_exited(pidfd, 0),
+        None => handle.start_time.is_some() && exit_code(handle).is_some(),
+    }
+}
+
+/// Resolves the `/proc/<pid>/exe` link of the process and reads the architecture
+/// from the ELF header of the executable.
+pub(crate) fn process_details(pid: u32) -> (Option<PathBuf>, Option<Architecture>) {
+    let path = fs::read_link(RawHandle::bare(pid).proc_path("exe")).ok();
+    (path, process_architecture(pid))
+}
+
+/// Reads the ELF header of the executable of the process.
+pub(crate) fn process_architecture(pid: u32) -> Option<Architecture> {
+    let mut header = [0u8; 20];
+    File::open(RawHandle::bare(pid).proc_path("exe"))
+        .ok()?
+        .read_exact(&mut header)
+        .ok()?;
+    elf_architecture(&header)
+}
+
+/// Returns the architecture of an opened process for [`Process`](crate::Process),
+/// which the handle read when it was opened.
+pub(crate) fn architecture(handle: &RawHandle, _pid: u32) -> Option<Architecture> {
+    handle.architecture
+}
+
+/// Maps the `e_machine` field of an ELF header onto [`Architecture`].
+pub(crate) fn elf_architecture(header: &[u8]) -> Option<Architecture> {
+    if header.get(..4)? != b"\x7FELF" {
+        return None;
+    }
+    let machine = [*header.get(18)?, *header.get(19)?];
+    let machine = match header.get(5)? {
+        2 => u16::from_be_bytes(machine),
+        _ => u16::from_le_bytes(machine),
+    };
+    match machine {
+        3 => Some(Architecture::X86),
+        40 => Some(Architecture::Arm),
+        62 => Some(Architecture::X86_64),
+        183 => Some(Architecture::Aarch64),
+        _ => None,
+    }
+}
+
+/// Reads the executable name of the process behind `handle`.
+fn process_name_of(handle: RawHandle) -> Option<String> {
+    fs::read_link(handle.proc_path("exe"))
+        .ok()
+        .and_then(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .or_else(|| {
+            fs::read_to_string(handle.proc_path("comm"))
+                .ok()
+                .map(|comm| comm.trim_end().to_string())
+        })
+}
+
+/// Parses the contents of `/proc/<pid>/maps` into regions and their backing paths.
+///
+/// Each line has the form `start-end perms offset dev inode [path]`. Anonymous
+/// mappings yield `None`, pseudo paths such as `[heap]` are returned as-is.
+pub(crate) fn parse_maps(maps: &str) -> Vec<(MemoryRegion, Option<&str>)> {
+    maps.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(6, ' ');
+            let (start, end) = fields.next()?.split_once('-')?;
+            let perms = fields.next()?.as_bytes();
+            let path = fields.nth(3).map(str::trim_start).filter(|p| !p.is_empty());
+
+            let base = usize::from_str_radix(start, 16).ok()?;
+            let end = usize::from_str_radix(end, 16).ok()?;
+            let region = MemoryRegion {
+                base,
+                size: end.checked_sub(base)?,
+                protection: Protection {
+                    read: perms.first() == Some(&b'r'),
+                    write: perms.get(1) == Some(&b'w'),
+                    execute: perms.get(2) == Some(&b'x'),
+                },
+            };
+            Some((region, path))
+        })
+        .collect()
+}
+
+/// Groups file-backed mappings into modules, one per mapped file.
+///
+/// The module spans from the lowest to the highest address mapped from the file.
+pub(crate) fn modules_from_maps(maps: &str) -> Vec<ModuleData> {
+    let mut order = Vec::new();
+    let mut spans: HashMap<&str, (usize, usize)> = HashMap::new();
+
+    for (region, path) in parse_maps(maps) {
+        let Some(path) = path.filter(|path| path.starts_with('/')) else {
+            continue;
+        };
+        let path = path.trim_end_matches(" (deleted)");
+
+        spans
+            .entry(path)
+            .and_modify(|(start, end)| {
+                *start = (*start).min(region.base);
+                *end = (*end).max(region.end());
+            })
+            .or_insert_with(|| {
+                order.push(path);
+                (region.base, region.end())
+            });
+    }
+
+    order
+        .into_iter()
+        .map(|path| {
+            let (start, end) = spans[path];
+            ModuleData {
+                module_name: Path::new(path)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("<Module Name>")
+                    .to_ascii_lowercase(),
+                module_addr: start,
+                module_size: end - start,
+            }
+        })
+        .collect()
+}
+
+/// Linux backend built on `process_vm_readv`, `process_vm_writev` and `/proc/<pid>/maps`.
+impl MemoryAccess for RawHandle {
+    fn read_bytes(&self, addr: usize, buffer: &mut [u8]) -> io::Result<usize> {
+        let local = iovec {
+            iov_base: buffer.as_mut_ptr().cast(),
+            iov_len: buffer.len(),
+        };
+        let remote = iovec {
+            iov_base: addr as *mut _,
+            iov_len: buffer.len(),
+        };
+
+        let transferred = unsafe { process_vm_readv(self.pid, &local, 1, &remote, 1, 0) };
+        usize::try_from(transferred).map_err(|_| io::Error::last_os_error())
+    }
+
+    fn write_bytes(&self, addr: usize, buffer: &[u8]) -> io::Result<usize> {
+        let local = iovec {
+            iov_base: buffer.as_ptr().cast_mut().cast(),
+            iov_len: buffer.len(),
+        };
+        let remote = iovec {
+            iov_base: addr as *mut _,
+            iov_len: buffer.len(),
+        };
+
+        let transferred = unsafe { process_vm_writev(self.pid, &local, 1, &remote, 1, 0) };
+        usize::try_from(transferred).map_err(|_| io::Error::last_os_error())
+    }
+
+    /// Passes up to `UIO_MAXIOV` requests to a single `process_vm_readv` call. The
+    /// kernel stops at the first request it cannot read completely, which is then
+    /// read on its own to report its error, and the call resumes after it.
+    fn read_scattered(&self, requests: &mut [(usize, &mut [u8])]) -> Vec<io::Result<usize>> {
+        let mut results = Vec::with_capacity(requests.len());
+
+        while results.len() < requests.len() {
+            let start = results.len();
+            let end = requests.len().min(start + UIO_MAXIOV as usize);
+            let batch = &mut requests[start..end];
+            let local: Vec<iovec> = batch
+                .iter_mut()
+                .map(|(_, buffer)| iovec {
+                    iov_base: buffer.as_mut_ptr().cast(),
+                    iov_len: buffer.len(),
+                })
+                .collect();
+            let remote: Vec<iovec> = batch
+                .iter()
+                .map(|(addr, buffer)| iovec {
+                    iov_base: *addr as *mut _,
+                    iov_len: buffer.len(),
+                })
+                .collect();
+
+            let transferred = unsafe {
+                process_vm_readv(
+                    self.pid,
+                    local.as_ptr(),
+                    local.len() as _,
+                    remote.as_ptr(),
+                    remote.len() as _,
+                    0,
+                )
+            };
+            let Ok(mut transferred) = usize::try_from(transferred) else {
+                results.push(Err(io::Error::last_os_error()));
+                continue;
+            };
+
+            for (addr, buffer) in batch {
+                if transferred < buffer.len() {
+                    results.push(self.read_bytes(*addr, buffer));
+                    break;
+                }
+                transferred -= buffer.len();
+                results.push(Ok(buffer.len()));
+            }
+        }
+        results
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        fs::read_to_string(self.proc_path("maps"))
+            .map(|maps| {
+                parse_maps(&maps)
+                    .into_iter()
+                    .map(|(region, _)| region)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn modules(&self) -> Vec<ModuleData> {
+        fs::read_to_string(self.proc_path("maps"))
+            .map(|maps| modules_from_maps(&maps))
+            .unwrap_or_default()
+    }
+
+    fn pid(&self) -> Option<u32> {
+        Some(RawHandle::pid(self))
+    }
+
+    fn architecture(&self) -> Option<Architecture> {
+        self.architecture
+    }
+}
@@ -0,0 +1,196 @@
+use std::io;
+
+use crate::{
+    errors::{Errors, OsError},
+    types::{Architecture, MemoryRegion, ModuleData},
+};
+
+/// The granularity of memory protection assumed by reads that must not run into an
+/// unmapped page. 4 KiB is the smallest page size of every supported platform.
+pub(crate) const PAGE_SIZE: usize = 0x1000;
+
+/// The largest gap between two ranges that the default
+/// [`read_scattered`](MemoryAccess::read_scattered) reads along instead of issuing
+/// another call. Copying a few hundred bytes is cheaper than a system call.
+pub const COALESCE_GAP: usize = 256;
+
+/// Platform-independent access to the address space of a target process.
+///
+/// Every high-level operation of this crate ([`read`](crate::read),
+/// [`write`](crate::write), [`find_signature`](crate::utils::find_signature) and
+/// [`process_modules`](crate::utils::process_modules)) is written against this trait,
+/// so the same code runs on top of the Win32 API, the Linux `/proc` interface or
+/// any custom backend.
+///
+/// # Implementors
+///
+/// * [`Process`](crate::Process) - an opened process on the current platform.
+/// * `HANDLE` (Windows) - a raw process handle, backed by `ReadProcessMemory` & co.
+/// * [`RawHandle`](crate::linux::RawHandle) (Linux) - a PID, backed by
+///   `process_vm_readv`/`process_vm_writev` and `/proc/<pid>/maps`.
+pub trait MemoryAccess {
+    /// Copies memory starting at `addr` into `buffer`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of bytes actually read. It may be less than
+    ///   `buffer.len()` if the range runs into unreadable memory (partial copy).
+    /// * `Err(io::Error)` - Nothing could be read; carries the OS error code.
+    fn read_bytes(&self, addr: usize, buffer: &mut [u8]) -> io::Result<usize>;
+
+    /// Copies `buffer` into the target memory starting at `addr`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of bytes actually written, possibly less than
+    ///   `buffer.len()` on a partial copy.
+    /// * `Err(io::Error)` - Nothing could be written; carries the OS error code.
+    fn write_bytes(&self, addr: usize, buffer: &[u8]) -> io::Result<usize>;
+
+    /// Enumerates the committed memory regions of the target, sorted by base address.
+    fn regions(&self) -> Vec<MemoryRegion>;
+
+    /// Enumerates the modules (executable and shared libraries) loaded by the target.
+    fn modules(&self) -> Vec<ModuleData>;
+
+    /// Returns the PID of the target, if the backend knows it. Errors of the
+    /// provided methods report it for context.
+    fn pid(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns the architecture of the target, if known.
+    ///
+    /// Pointer chains ask once per dereferenced pointer, so implementations should
+    /// answer from a cache rather than query the OS. The default implementation returns `None`, which makes
+    /// [`pointer_width`](Self::pointer_width) fall back to the pointer width of the
+    /// current process.
+    fn architecture(&self) -> Option<Architecture> {
+        None
+    }
+
+    /// Returns the size of a pointer in the target in bytes (4 or 8).
+    ///
+    /// Pointer chains, [`PointerPath`](crate::PointerPath)s and address expressions
+    /// dereference pointers of this width, so 32-bit (WOW64) targets work from a
+    /// 64-bit tool.
+    fn pointer_width(&self) -> usize {
+        self.architecture()
+            .map_or(size_of::<usize>(), Architecture::pointer_width)
+    }
+
+    /// Looks up a loaded module by its name (case-insensitive).
+    ///
+    /// The default implementation searches [`modules`](Self::modules); backends that
+    /// cache their module list should override it.
+    fn find_module(&self, name: &str) -> Option<ModuleData> {
+        self.modules()
+            .into_iter()
+            .find(|module| module.module_name.eq_ignore_ascii_case(name))
+    }
+
+    /// Reads many small ranges with as few OS calls as possible.
+    ///
+    /// Every request is an address and the buffer to fill from it. The result of each
+    /// request is reported at its index, exactly as [`read_bytes`](Self::read_bytes)
+    /// would report it, so one unreadable address only fails its own request.
+    ///
+    /// The default implementation sorts the requests by address and reads ranges
+    /// that overlap or lie at most [`COALESCE_GAP`] bytes apart with a single
+    /// `read_bytes` call. Requests a coalesced read did not cover completely are read
+    /// again on their own. Backends that can scatter natively override it, e.g. Linux
+    /// with the iovecs of `process_vm_readv`.
+    fn read_scattered(&self, requests: &mut [(usize, &mut [u8])]) -> Vec<io::Result<usize>> {
+        read_coalesced(self, requests)
+    }
+
+    /// Fills the whole `buffer` from `addr` or reports why it could not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::ReadFailed`] with the PID, the failing address, the number of
+    /// bytes that were copied before the failure and the OS error, if any.
+    fn read_exact(&self, addr: usize, buffer: &mut [u8]) -> Result<(), Errors> {
+        let (transferred, os_error) = match self.read_bytes(addr, buffer) {
+            Ok(transferred) if transferred == buffer.len() => return Ok(()),
+            Ok(transferred) => (transferred, None),
+            Err(err) => (0, OsError::of(&err)),
+        };
+        Err(Errors::ReadFailed {
+            pid: self.pid(),
+            address: addr,
+            requested: buffer.len(),
+            transferred,
+            os_error,
+        })
+    }
+
+    /// Writes the whole `buffer` to `addr` or reports why it could not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::WriteFailed`] with the PID, the failing address, the number of
+    /// bytes that were copied before the failure and the OS error, if any.
+    fn write_all(&self, addr: usize, buffer: &[u8]) -> Result<(), Errors> {
+        let (transferred, os_error) = match self.write_bytes(addr, buffer) {
+            Ok(transferred) if transferred == buffer.len() => return Ok(()),
+            Ok(transferred) => (transferred, None),
+            Err(err) => (0, OsError::of(&err)),
+        };
+        Err(Errors::WriteFailed {
+            pid: self.pid(),
+            address: addr,
+            requested: buffer.len(),
+            transferred,
+            os_error,
+        })
+    }
+}
+
+/// The default [`MemoryAccess::read_scattered`]: one `read_bytes` call per group of
+/// close ranges, falling back to one call per request for what a group read missed.
+fn read_coalesced<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    requests: &mut [(usize, &mut [u8])],
+) -> Vec<io::Result<usize>> {
+    let mut results: Vec<io::Result<usize>> = requests.iter().map(|_| Ok(0)).collect();
+    let mut order: Vec<usize> = (0..requests.len())
+        .filter(|&index| !requests[index].1.is_empty())
+        .collect();
+    order.sort_by_key(|&index| requests[index].0);
+
+    let end_of = |(addr, buffer): &(usize, &mut [u8])| addr.saturating_add(buffer.len());
+    let mut span = Vec::new();
+    let mut first = 0;
+
+    while first < order.len() {
+        let base = requests[order[first]].0;
+        let mut end = end_of(&requests[order[first]]);
+        let mut last = first + 1;
+        while last < order.len() && requests[order[last]].0 <= end.saturating_add(COALESCE_GAP) {
+            end = end.max(end_of(&requests[order[last]]));
+            last += 1;
+        }
+
+        if let [index] = order[first..last] {
+            let (addr, buffer) = &mut requests[index];
+            results[index] = memory.read_bytes(*addr, buffer);
+        } else {
+            span.resize(end - base, 0);
+            let read = memory.read_bytes(base, &mut span).unwrap_or(0);
+
+            for &index in &order[first..last] {
+                let (addr, buffer) = &mut requests[index];
+                let offset = *addr - base;
+                results[index] = if offset + buffer.len() <= read {
+                    buffer.copy_from_slice(&span[offset..offset + buffer.len()]);
+                    Ok(buffer.len())
+                } else {
+                    memory.read_bytes(*addr, buffer)
+                };
+            }
+        }
+        first = last;
+    }
+    results
+}
@@ -0,0 +1,228 @@
+use std::{io, sync::Mutex};
+
+use crate::{
+    memory::MemoryAccess,
+    types::{Architecture, MemoryRegion, ModuleData, Protection},
+};
+
+/// A fabricated address space for deterministic tests of user code.
+///
+/// `MockProcess` implements [`MemoryAccess`] on top of plain byte buffers, so pointer
+/// chains, signature scans and writes can be exercised on any machine without a
+/// running target.
+///
+/// # Behavior
+///
+/// * **Reads** copy bytes until the first unmapped or unreadable address. A read that
+///   starts there fails, a read that runs into it is reported as a partial copy.
+/// * **Writes** follow the same rules with the `write` protection flag.
+/// * [`poke`](Self::poke) and [`peek`](Self::peek) bypass protections to set up and
+///   inspect fixtures.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::{mock::MockProcess, read, types::Protection};
+///
+/// let process = MockProcess::new()
+///     .region(0x1000, 0x2000usize.to_ne_bytes(), Protection::RW)
+///     .region(0x2000, [0u8; 0x20], Protection::RW)
+///     .module("game.exe", 0x1000, 0x1000);
+/// process.poke(0x2010, &100usize.to_ne_bytes());
+///
+/// // [game.exe + 0] + 0x10
+/// let mut value = 0usize;
+/// read(&process, 0x1000, &[0x10], &mut value);
+/// assert_eq!(value, 100);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockProcess {
+    regions: Mutex<Vec<(MemoryRegion, Vec<u8>)>>,
+    modules: Vec<ModuleData>,
+    architecture: Option<Architecture>,
+}
+
+impl MockProcess {
+    /// Creates an empty address space without regions or modules.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `bytes` at `base` with the given `protection`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region is empty or overlaps an already mapped region.
+    #[must_use]
+    pub fn region(self, base: usize, bytes: impl Into<Vec<u8>>, protection: Protection) -> Self {
+        let bytes = bytes.into();
+        let region = MemoryRegion {
+            base,
+            size: bytes.len(),
+            protection,
+        };
+        assert!(region.size > 0, "mock region at {base:#X} is empty");
+
+        {
+            let mut regions = self.lock();
+            assert!(
+                regions
+                    .iter()
+                    .all(|(other, _)| region.end() <= other.base || other.end() <= region.base),
+                "mock region at {base:#X} overlaps an existing region"
+            );
+            let index = regions.partition_point(|(other, _)| other.base < base);
+            regions.insert(index, (region, bytes));
+        }
+        self
+    }
+
+    /// Registers a module named `name` spanning `size` bytes from `base`.
+    ///
+    /// The module does not need to be backed by a region, but reads of its memory
+    /// only succeed if it is.
+    #[must_use]
+    pub fn module(mut self, name: &str, base: usize, size: usize) -> Self {
+        self.modules.push(ModuleData {
+            module_name: name.to_ascii_lowercase(),
+            module_addr: base,
+            module_size: size,
+        });
+        self
+    }
+
+    /// Pretends the target runs as `architecture`, e.g. [`Architecture::X86`] to test
+    /// pointer chains of 32-bit games with 4-byte pointers.
+    #[must_use]
+    pub fn architecture(mut self, architecture: Architecture) -> Self {
+        self.architecture = Some(architecture);
+        self
+    }
+
+    /// Overwrites memory at `addr` regardless of protections.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any byte of the range is not mapped.
+    pub fn poke(&self, addr: usize, bytes: &[u8]) {
+        let copied = self.transfer(
+            addr,
+            bytes.len(),
+            |_| true,
+            |chunk, offset| {
+                chunk.copy_from_slice(&bytes[offset..offset + chunk.len()]);
+            },
+        );
+        assert_eq!(copied, bytes.len(), "mock poke at {addr:#X} is not mapped");
+    }
+
+    /// Returns `len` bytes at `addr` regardless of protections, or `None` if any byte
+    /// of the range is not mapped.
+    #[must_use]
+    pub fn peek(&self, addr: usize, len: usize) -> Option<Vec<u8>> {
+        let mut bytes = vec![0u8; len];
+        let copied = self.transfer(
+            addr,
+            len,
+            |_| true,
+            |chunk, offset| {
+                bytes[offset..offset + chunk.len()].copy_from_slice(chunk);
+            },
+        );
+        (copied == len).then_some(bytes)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(MemoryRegion, Vec<u8>)>> {
+        self.regions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Walks the mapped bytes of `[addr, addr + len)` in order and hands each
+    /// contiguous chunk to `copy` together with its offset from `addr`.
+    ///
+    /// Stops at the first gap or region rejected by `allowed` and returns the number
+    /// of bytes handed out.
+    fn transfer(
+        &self,
+        addr: usize,
+        len: usize,
+        allowed: impl Fn(Protection) -> bool,
+        mut copy: impl FnMut(&mut [u8], usize),
+    ) -> usize {
+        let mut regions = self.lock();
+        let mut done = 0;
+
+        while done < len {
+            let current = addr.wrapping_add(done);
+            let Some((region, bytes)) = regions
+                .iter_mut()
+                .find(|(region, _)| region.base <= current && current < region.end())
+            else {
+                break;
+            };
+            if !allowed(region.protection) {
+                break;
+            }
+
+            let start = current - region.base;
+            let count = (len - done).min(region.size - start);
+            copy(&mut bytes[start..start + count], done);
+            done += count;
+        }
+        done
+    }
+}
+
+/// Builds the error returned when nothing could be transferred.
+fn unmapped(addr: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("address {addr:#X} is not accessible in the mock process"),
+    )
+}
+
+impl MemoryAccess for MockProcess {
+    fn read_bytes(&self, addr: usize, buffer: &mut [u8]) -> io::Result<usize> {
+        let copied = self.transfer(
+            addr,
+            buffer.len(),
+            |protection| protection.read,
+            |chunk, offset| buffer[offset..offset + chunk.len()].copy_from_slice(chunk),
+        );
+
+        if copied == 0 && !buffer.is_empty() {
+            Err(unmapped(addr))
+        } else {
+            Ok(copied)
+        }
+    }
+
+    fn write_bytes(&self, addr: usize, buffer: &[u8]) -> io::Result<usize> {
+        let copied = self.transfer(
+            addr,
+            buffer.len(),
+            |protection| protection.write,
+            |chunk, offset| chunk.copy_from_slice(&buffer[offset..offset + chunk.len()]),
+        );
+
+        if copied == 0 && !buffer.is_empty() {
+            Err(unmapped(addr))
+        } else {
+            Ok(copied)
+        }
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        self.lock().iter().map(|(region, _)| *region).collect()
+    }
+
+    fn modules(&self) -> Vec<ModuleData> {
+        self.modules.clone()
+    }
+
+    fn architecture(&self) -> Option<Architecture> {
+        self.architecture
+    }
+}
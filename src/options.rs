@@ -0,0 +1,176 @@
+use std::fmt::Display;
+
+use crate::{errors::Errors, platform, process::Process};
+
+/// A single access right a [`Process`] can be opened with.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Reading memory.
+    Read,
+    /// Writing memory.
+    Write,
+    /// Enumerating modules and memory regions.
+    Query,
+    /// Changing the memory layout, e.g. page protections.
+    Operation,
+    /// Waiting for the process to exit.
+    Synchronize,
+}
+
+impl Access {
+    /// Every right, in the order [`OpenOptions::open`] checks them.
+    pub(crate) const ALL: [Access; 5] = [
+        Access::Read,
+        Access::Write,
+        Access::Query,
+        Access::Operation,
+        Access::Synchronize,
+    ];
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Access::Read => "read",
+            Access::Write => "write",
+            Access::Query => "query",
+            Access::Operation => "operation",
+            Access::Synchronize => "synchronize",
+        })
+    }
+}
+
+/// Options and access rights used to open a [`Process`].
+///
+/// Modeled after [`std::fs::OpenOptions`]: start from [`OpenOptions::new`], which
+/// requests nothing, enable the rights the tool actually needs and call
+/// [`open`](Self::open). Requesting less than full access lets read-only tools
+/// open processes that deny `PROCESS_ALL_ACCESS` and keeps them off EDR radars.
+///
+/// # Rights
+///
+/// | Right | Windows | Linux check |
+/// |---|---|---|
+/// | `read` | `PROCESS_VM_READ` | `/proc/<pid>/mem` readable |
+/// | `write` | `PROCESS_VM_WRITE \| PROCESS_VM_OPERATION` | `/proc/<pid>/mem` writable |
+/// | `query` | `PROCESS_QUERY_INFORMATION \| PROCESS_VM_READ` | `/proc/<pid>/maps` readable |
+/// | `operation` | `PROCESS_VM_OPERATION` | - |
+/// | `synchronize` | `SYNCHRONIZE` | - |
+///
+/// On Windows every handle also gets `PROCESS_QUERY_LIMITED_INFORMATION`, which is
+/// needed to detect that the target exited. `query` includes `PROCESS_VM_READ`,
+/// since listing modules reads the loader data of the target, so a handle opened
+/// for `query` alone can read memory through the OS, though not through
+/// [`Process`]. When `OpenProcess` refuses the handle, each requested right is
+/// tried on its own to report the one that was refused.
+///
+/// The Linux checks go through the same ptrace access mode checks as
+/// `process_vm_readv`/`process_vm_writev`, so a denied right is reported on open
+/// instead of on the first read. Module and region enumeration need `query`;
+/// without it the module list of the opened process stays empty.
+///
+/// # Example
+///
+/// ```no_run
+/// use gamehack_librs::OpenOptions;
+///
+/// let overlay = OpenOptions::new().read(true).query(true).open(1234).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpenOptions {
+    read: bool,
+    write: bool,
+    query: bool,
+    operation: bool,
+    synchronize: bool,
+}
+
+impl OpenOptions {
+    /// Creates a blank set of options that requests no rights.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates options that request every right, as used by [`Process::open`].
+    #[must_use]
+    pub fn all() -> Self {
+        Self {
+            read: true,
+            write: true,
+            query: true,
+            operation: true,
+            synchronize: true,
+        }
+    }
+
+    /// Requests the right to read memory.
+    pub fn read(&mut self, read: bool) -> &mut Self {
+        self.read = read;
+        self
+    }
+
+    /// Requests the right to write memory.
+    pub fn write(&mut self, write: bool) -> &mut Self {
+        self.write = write;
+        self
+    }
+
+    /// Requests the right to enumerate modules and memory regions.
+    pub fn query(&mut self, query: bool) -> &mut Self {
+        self.query = query;
+        self
+    }
+
+    /// Requests the right to change the memory layout of the process.
+    pub fn operation(&mut self, operation: bool) -> &mut Self {
+        self.operation = operation;
+        self
+    }
+
+    /// Requests the right to wait for the process to exit.
+    pub fn synchronize(&mut self, synchronize: bool) -> &mut Self {
+        self.synchronize = synchronize;
+        self
+    }
+
+    /// Creates options that request only `access`.
+    #[cfg(windows)]
+    pub(crate) fn only(access: Access) -> Self {
+        let mut options = Self::new();
+        match access {
+            Access::Read => options.read = true,
+            Access::Write => options.write = true,
+            Access::Query => options.query = true,
+            Access::Operation => options.operation = true,
+            Access::Synchronize => options.synchronize = true,
+        }
+        options
+    }
+
+    /// Returns `true` if `access` is requested by these options.
+    #[must_use]
+    pub fn allows(&self, access: Access) -> bool {
+        match access {
+            Access::Read => self.read,
+            Access::Write => self.write,
+            Access::Query => self.query,
+            Access::Operation => self.operation,
+            Access::Synchronize => self.synchronize,
+        }
+    }
+
+    /// Opens the process with the given `pid` with the requested rights.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::ProcessNotFound`] if no process with this PID exists.
+    /// * [`Errors::AccessDenied`] with the first right that was refused (if the
+    ///   platform can tell) and the OS error.
+    pub fn open(&self, pid: u32) -> Result<Process, Errors> {
+        let mut process = Process::from_handle(platform::open_process(pid, self)?, pid, *self);
+        process.refresh_modules();
+        Ok(process)
+    }
+}
@@ -0,0 +1,415 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use crate::errors::Errors;
+
+/// A byte pattern (signature) with per-nibble wildcards for
+/// [`find_signature`](crate::utils::find_signature).
+///
+/// A pattern stores the expected bytes together with a mask per byte: a byte of
+/// memory matches if `memory & mask == byte`. Full wildcards have a mask of `0x00`,
+/// nibble wildcards such as `4?` a mask of `0xF0`.
+///
+/// # Notation
+///
+/// [`parse`](Self::parse) accepts the IDA and x64dbg style:
+///
+/// * `48 8D 05 ? ? ? ? 48 89 41 18` - bytes in hex, `?` or `??` for any byte.
+/// * `4? 8B ?5` - `?` in place of one hex digit matches any value of that nibble.
+/// * `488D05????????` - tokens of several bytes without spaces.
+///
+/// [`from_code`](Self::from_code) accepts the byte string and `x`/`?` mask pairs
+/// found in C++ code. Patterns known at compile time are best written with
+/// [`pattern!`](crate::pattern!), which reports typos as build errors.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::pattern::Pattern;
+///
+/// let ida = Pattern::parse("48 8D 05 ? ? ? ? 48 89 41 18").unwrap();
+/// let code = Pattern::from_code(b"\x48\x8D\x05\0\0\0\0\x48\x89\x41\x18", "xxx????xxxx").unwrap();
+/// assert_eq!(ida, code);
+/// assert!(ida.matches(b"\x48\x8D\x05\x7A\xB9\xA6\x01\x48\x89\x41\x18"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    /// The expected bytes, with the wildcard bits cleared.
+    bytes: Cow<'static, [u8]>,
+    /// The bits of every byte that have to match.
+    mask: Cow<'static, [u8]>,
+    /// The index of the exact byte [`find`](Self::find) looks for first, the one
+    /// least common in machine code; `None` if every byte has wildcard bits.
+    anchor: Option<usize>,
+}
+
+impl Pattern {
+    /// Parses an IDA/x64dbg style pattern such as `"48 8D 05 ? ? ? ? 48 89 41 18"`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::InvalidPattern`] with the offending token if it is neither a
+    ///   wildcard nor hex digits, or has an odd number of digits.
+    /// * [`Errors::EmptyPattern`] if the pattern has no tokens.
+    pub fn parse(pattern: &str) -> Result<Self, Errors> {
+        let mut bytes = Vec::new();
+        let mut mask = Vec::new();
+
+        for token in pattern.split_whitespace() {
+            if token == "?" {
+                bytes.push(0);
+                mask.push(0);
+                continue;
+            }
+
+            let digits = token.as_bytes();
+            if digits.len() % 2 != 0 {
+                return Err(Errors::InvalidPattern(token.to_string()));
+            }
+            for pair in digits.chunks_exact(2) {
+                let (high, high_mask) = parse_nibble(pair[0], token)?;
+                let (low, low_mask) = parse_nibble(pair[1], token)?;
+                bytes.push(high << 4 | low);
+                mask.push(high_mask << 4 | low_mask);
+            }
+        }
+        Self::new(bytes, mask)
+    }
+
+    /// Builds a pattern from a byte string and a mask of the same length, where `x`
+    /// marks a byte that must match and `?` a wildcard, e.g.
+    /// `(b"\x48\x8D\x05\0\0\0\0", "xxx????")`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::PatternLengthMismatch`] if `bytes` and `mask` differ in length.
+    /// * [`Errors::InvalidPattern`] with the offending character if `mask` contains
+    ///   anything but `x` and `?`.
+    /// * [`Errors::EmptyPattern`] if both are empty.
+    pub fn from_code(bytes: &[u8], mask: &str) -> Result<Self, Errors> {
+        if bytes.len() != mask.len() {
+            return Err(Errors::PatternLengthMismatch {
+                bytes: bytes.len(),
+                mask: mask.len(),
+            });
+        }
+
+        let mask = mask
+            .chars()
+            .map(|c| match c {
+                'x' => Ok(0xFF),
+                '?' => Ok(0x00),
+                c => Err(Errors::InvalidPattern(c.to_string())),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let bytes = bytes
+            .iter()
+            .zip(&mask)
+            .map(|(byte, mask)| byte & mask)
+            .collect();
+        Self::new(bytes, mask)
+    }
+
+    /// Builds a pattern from static bytes and masks in a `const` context, e.g. for
+    /// a `static`. [`pattern!`](crate::pattern!) uses it with the output of its
+    /// compile-time parser.
+    ///
+    /// # Panics
+    ///
+    /// Panics, at compile time in `const` contexts, if the slices are empty, differ
+    /// in length or `bytes` has bits set that `mask` ignores.
+    #[must_use]
+    pub const fn from_static(bytes: &'static [u8], mask: &'static [u8]) -> Self {
+        assert!(!bytes.is_empty(), "pattern is empty");
+        assert!(
+            bytes.len() == mask.len(),
+            "pattern bytes and mask differ in length"
+        );
+        let mut index = 0;
+        while index < bytes.len() {
+            assert!(
+                bytes[index] & !mask[index] == 0,
+                "pattern byte has bits outside of its mask"
+            );
+            index += 1;
+        }
+
+        Self {
+            bytes: Cow::Borrowed(bytes),
+            mask: Cow::Borrowed(mask),
+            anchor: anchor(bytes, mask),
+        }
+    }
+
+    fn new(bytes: Vec<u8>, mask: Vec<u8>) -> Result<Self, Errors> {
+        if bytes.is_empty() {
+            return Err(Errors::EmptyPattern);
+        }
+        Ok(Self {
+            anchor: anchor(&bytes, &mask),
+            bytes: Cow::Owned(bytes),
+            mask: Cow::Owned(mask),
+        })
+    }
+
+    /// Returns the length of the pattern in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Always `false`, since empty patterns are rejected when they are built.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the expected bytes, with wildcard bits set to 0.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the mask of every byte: `0xFF` for exact bytes, `0x00` for wildcards
+    /// and `0xF0`/`0x0F` for nibble wildcards.
+    #[must_use]
+    pub fn mask(&self) -> &[u8] {
+        &self.mask
+    }
+
+    /// Returns `true` if `data` starts with bytes matching the pattern.
+    ///
+    /// Compares eight bytes at a time.
+    #[must_use]
+    pub fn matches(&self, data: &[u8]) -> bool {
+        let Some(data) = data.get(..self.len()) else {
+            return false;
+        };
+        let data = data.chunks_exact(8);
+        let bytes = self.bytes.chunks_exact(8);
+        let mask = self.mask.chunks_exact(8);
+
+        data.remainder()
+            .iter()
+            .zip(bytes.remainder().iter().zip(mask.remainder()))
+            .all(|(data, (byte, mask))| data & mask == *byte)
+            && data
+                .zip(bytes.zip(mask))
+                .all(|(data, (byte, mask))| word(data) & word(mask) == word(byte))
+    }
+
+    /// Returns the position of the first match of the pattern in `haystack`.
+    ///
+    /// Only positions holding the rarest exact byte of the pattern are compared,
+    /// and [`memchr`](memchr::memchr) finds those with SIMD instructions where the
+    /// CPU has them. Patterns without an exact byte are compared at every position.
+    ///
+    /// ```
+    /// use gamehack_librs::pattern;
+    ///
+    /// let code = b"\x48\x8B\x05\x48\x8D\x05\x10\x20\x30\x40\xC3";
+    /// assert_eq!(pattern!("48 8D 05 ? ? ? ? C3").find(code), Some(3));
+    /// assert_eq!(pattern!("48 8D 0D").find(code), None);
+    /// ```
+    #[must_use]
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let last = haystack.len().checked_sub(self.len())?;
+        let Some(anchor) = self.anchor else {
+            return (0..=last).find(|&start| self.matches(&haystack[start..]));
+        };
+
+        let mut start = 0;
+        while start <= last {
+            let found = memchr::memchr(
+                self.bytes[anchor],
+                &haystack[start + anchor..=last + anchor],
+            )?;
+            let candidate = start + found;
+            if self.matches(&haystack[candidate..]) {
+                return Some(candidate);
+            }
+            start = candidate + 1;
+        }
+        None
+    }
+}
+
+/// Reads eight bytes as one word, so they can be masked and compared at once.
+fn word(bytes: &[u8]) -> u64 {
+    u64::from_ne_bytes(bytes.try_into().expect("chunk of eight bytes"))
+}
+
+/// Returns the index of the exact byte of a pattern that is least common in
+/// machine code, or `None` if every byte has wildcard bits.
+const fn anchor(bytes: &[u8], mask: &[u8]) -> Option<usize> {
+    let mut anchor = None;
+    let mut best = u8::MAX;
+    let mut index = 0;
+    while index < bytes.len() {
+        if mask[index] == 0xFF && (anchor.is_none() || frequency(bytes[index]) < best) {
+            anchor = Some(index);
+            best = frequency(bytes[index]);
+        }
+        index += 1;
+    }
+    anchor
+}
+
+/// Ranks how common `byte` is in x86 code and data, from 0 (rare) to 3.
+const fn frequency(byte: u8) -> u8 {
+    match byte {
+        // Padding, zeroed immediates and `int3`.
+        0x00 | 0xFF | 0xCC => 3,
+        // REX prefixes, `mov`, `lea`, `call`, `jmp`, two-byte opcodes, SIB bytes.
+        0x0F
+        | 0x24
+        | 0x40..=0x4F
+        | 0x83
+        | 0x85
+        | 0x89
+        | 0x8B
+        | 0x8D
+        | 0x90
+        | 0xC3
+        | 0xE8
+        | 0xE9
+        | 0xEB => 2,
+        // Small immediates and displacements.
+        0x01..=0x20 => 1,
+        _ => 0,
+    }
+}
+
+/// Parses one hex digit or `?` of `token`, returning its value and mask.
+fn parse_nibble(digit: u8, token: &str) -> Result<(u8, u8), Errors> {
+    nibble(digit).ok_or_else(|| Errors::InvalidPattern(token.to_string()))
+}
+
+/// Returns the value and mask of a hex digit or `?`, or `None` for anything else.
+const fn nibble(digit: u8) -> Option<(u8, u8)> {
+    match digit {
+        b'?' => Some((0, 0)),
+        b'0'..=b'9' => Some((digit - b'0', 0xF)),
+        b'a'..=b'f' => Some((digit - b'a' + 10, 0xF)),
+        b'A'..=b'F' => Some((digit - b'A' + 10, 0xF)),
+        _ => None,
+    }
+}
+
+/// The compile-time counterpart of [`Pattern::parse`] behind
+/// [`pattern!`](crate::pattern!).
+///
+/// Writes the bytes and masks of up to `N` pattern bytes and returns the total
+/// number of bytes, so `scan::<0>` measures a pattern. Invalid patterns panic,
+/// which is a build error in `const` contexts.
+#[doc(hidden)]
+#[must_use]
+pub const fn scan<const N: usize>(pattern: &str) -> (usize, [u8; N], [u8; N]) {
+    let pattern = pattern.as_bytes();
+    let mut bytes = [0u8; N];
+    let mut mask = [0u8; N];
+    let mut len = 0;
+    let mut index = 0;
+
+    while index < pattern.len() {
+        if pattern[index].is_ascii_whitespace() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < pattern.len() && !pattern[index].is_ascii_whitespace() {
+            index += 1;
+        }
+
+        if index - start == 1 && pattern[start] == b'?' {
+            if len < N {
+                bytes[len] = 0;
+                mask[len] = 0;
+            }
+            len += 1;
+            continue;
+        }
+        assert!(
+            (index - start) % 2 == 0,
+            "pattern token has an odd number of digits"
+        );
+
+        let mut pair = start;
+        while pair < index {
+            let (Some((high, high_mask)), Some((low, low_mask))) =
+                (nibble(pattern[pair]), nibble(pattern[pair + 1]))
+            else {
+                panic!("pattern contains a character that is neither a hex digit nor `?`");
+            };
+            if len < N {
+                bytes[len] = high << 4 | low;
+                mask[len] = high_mask << 4 | low_mask;
+            }
+            len += 1;
+            pair += 2;
+        }
+    }
+    assert!(len > 0, "pattern is empty");
+    (len, bytes, mask)
+}
+
+/// Parses an IDA/x64dbg style [`Pattern`] at compile time.
+///
+/// The pattern is validated while the crate is built, so invalid hex digits and odd
+/// tokens are build errors instead of scans that never match. The result is a
+/// constant expression and can initialize a `static`.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::{Pattern, pattern};
+///
+/// static PHITMAN_VFT: Pattern = pattern!("48 8D 05 ? ? ? ? 48 89 41 18");
+///
+/// assert_eq!(PHITMAN_VFT, Pattern::parse("48 8D 05 ? ? ? ? 48 89 41 18").unwrap());
+/// ```
+///
+/// Typos do not compile:
+///
+/// ```compile_fail
+/// let pattern = gamehack_librs::pattern!("48 8G 05");
+/// ```
+///
+/// ```compile_fail
+/// let pattern = gamehack_librs::pattern!("48 8D5");
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    ($pattern:expr) => {{
+        const LEN: usize = $crate::pattern::scan::<0>($pattern).0;
+        const PARSED: (usize, [u8; LEN], [u8; LEN]) = $crate::pattern::scan::<LEN>($pattern);
+        const PATTERN: $crate::pattern::Pattern =
+            $crate::pattern::Pattern::from_static(&PARSED.1, &PARSED.2);
+        PATTERN
+    }};
+}
+
+impl FromStr for Pattern {
+    type Err = Errors;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::parse(pattern)
+    }
+}
+
+/// Formats the pattern in IDA style, e.g. `48 8D 05 ? ? ? ? 4?`.
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (byte, mask)) in self.bytes.iter().zip(self.mask.iter()).enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            match mask {
+                0x00 => f.write_str("?")?,
+                0xFF => write!(f, "{byte:02X}")?,
+                0xF0 => write!(f, "{:X}?", byte >> 4)?,
+                _ => write!(f, "?{:X}", byte & 0xF)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+/// Marker for "plain old data": types that can be copied to and from the target as
+/// raw bytes.
+///
+/// Reads fill a value with whatever bytes the target holds, and writes send the
+/// bytes of a value to another address space. Both are only sound for types without
+/// invalid bit patterns and without meaning tied to the current process, so every
+/// read and write of this crate is bounded by `Pod`. It is implemented for the
+/// integer and floating point primitives and for arrays of `Pod` types.
+///
+/// Types such as `bool`, `char`, enums, references and raw pointers are
+/// deliberately excluded: a byte of `2` is not a valid `bool`, and a pointer of this
+/// process means nothing in the target. Read them as integers (`u8`, `u32`,
+/// `usize`) and convert after validating.
+///
+/// # Safety
+///
+/// Implementing `Pod` for a type asserts that
+///
+/// * every bit pattern of `size_of::<Self>()` bytes is a valid value,
+/// * it has no padding bytes, since writes copy every byte of the value,
+/// * it holds no references, pointers or other data tied to the current process.
+///
+/// A `#[repr(C)]` (or `#[repr(transparent)]`) struct of `Pod` fields without padding
+/// satisfies these rules. Rust-layout structs do not, because their layout is
+/// unspecified.
+///
+/// # Deriving
+///
+/// `#[derive(Pod)]` checks these rules at compile time: it requires a `C`,
+/// `transparent` or `packed` representation, `Pod` fields and a size equal to the
+/// sum of the field sizes. Types the derive cannot check, such as generic
+/// wrappers, need a hand-written `unsafe impl Pod` that upholds the rules above.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::{Pod, mock::MockProcess, read_value, types::Protection};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Pod)]
+/// #[repr(C)]
+/// struct Vector3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// let process = MockProcess::new().region(0x1000, [0u8; 12], Protection::RW);
+/// process.poke(0x1004, &2.5f32.to_ne_bytes());
+///
+/// let position: Vector3 = read_value(&process, 0x1000).unwrap();
+/// assert_eq!(position, Vector3 { x: 0.0, y: 2.5, z: 0.0 });
+/// ```
+///
+/// Types with invalid bit patterns are rejected at compile time:
+///
+/// ```compile_fail
+/// use gamehack_librs::{mock::MockProcess, read_value};
+///
+/// let alive: bool = read_value(&MockProcess::new(), 0x1000).unwrap();
+/// ```
+///
+/// So are derived structs with padding:
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, gamehack_librs::Pod)]
+/// #[repr(C)]
+/// struct Entity {
+///     id: u8,
+///     health: u32,
+/// }
+/// ```
+///
+/// and structs with a Rust layout:
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, gamehack_librs::Pod)]
+/// struct Entity {
+///     id: u32,
+///     health: u32,
+/// }
+/// ```
+///
+/// A hand-written implementation for a generic wrapper:
+///
+/// ```
+/// use gamehack_librs::Pod;
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(transparent)]
+/// struct Encrypted<T>(T);
+///
+/// // SAFETY: `repr(transparent)` over a single `Pod` field, so it shares the
+/// // layout, the valid bit patterns and the lack of padding of `T`.
+/// unsafe impl<T: Pod> Pod for Encrypted<T> {}
+/// ```
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),* $(,)?) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
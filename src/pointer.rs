@@ -0,0 +1,553 @@
+use std::{fmt::Display, hash::Hash, marker::PhantomData};
+
+use crate::{
+    errors::Errors,
+    memory::MemoryAccess,
+    pod::Pod,
+    read_pointer, read_slice, read_value,
+    remote::{RemoteStruct, read_struct},
+    try_write,
+};
+
+/// A multi-level pointer as found in Cheat Engine tables and ReClass.
+///
+/// A path consists of a base address, optionally relative to a module, and a list
+/// of offsets. Resolving it dereferences the current address as a pointer of the
+/// target's width and adds the next offset for every level; the last offset is
+/// added without dereferencing, so the result is the address of the final value.
+///
+/// # Notation
+///
+/// Both of the following describe `[["hitman3.exe" + 0x22BAF18] + 0x18] + 0x20`:
+///
+/// * Arrow form: `"hitman3.exe"+022BAF18 -> 18 -> 20`
+/// * Bracket form: `[["hitman3.exe"+022BAF18]+18]+20`
+///
+/// Numbers are hexadecimal with an optional `0x` prefix, as in Cheat Engine.
+/// Offsets may be negative (`-> -10`). Module names may be quoted and must be
+/// quoted if they contain `+`, `-` or brackets. Absolute bases such as
+/// `7FF6A0001000 -> 18` are accepted as well.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::PointerPath;
+///
+/// let path = PointerPath::parse(r#""hitman3.exe"+022BAF18 -> 0x18"#).unwrap();
+/// assert_eq!(path.module(), Some("hitman3.exe"));
+/// assert_eq!(path.offsets(), &[0x18]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerPath {
+    module: Option<String>,
+    base_offset: i64,
+    offsets: Vec<i64>,
+}
+
+impl PointerPath {
+    /// Builds a path from its parts without parsing.
+    #[must_use]
+    pub fn new(module: Option<&str>, base_offset: i64, offsets: &[i64]) -> Self {
+        Self {
+            module: module.map(str::to_string),
+            base_offset,
+            offsets: offsets.to_vec(),
+        }
+    }
+
+    /// Parses a path in Cheat Engine arrow or bracket notation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::InvalidPointerPath`] with the offending part of `path` for
+    /// malformed numbers, unbalanced brackets or more than one module in the base.
+    pub fn parse(path: &str) -> Result<Self, Errors> {
+        let path = path.trim();
+        if path.is_empty() {
+            return Err(Errors::InvalidPointerPath(path.to_string()));
+        }
+
+        let (base, offsets) = if path.starts_with('[') {
+            parse_brackets(path)?
+        } else {
+            let parts = split_unquoted(path, "->");
+            let offsets = parts[1..]
+                .iter()
+                .map(|offset| parse_signed(offset))
+                .collect::<Result<_, _>>()?;
+            (parts[0], offsets)
+        };
+
+        let (module, base_offset) = parse_base(base)?;
+        Ok(Self {
+            module: module.map(str::to_string),
+            base_offset,
+            offsets,
+        })
+    }
+
+    /// Returns the module the base is relative to, if any.
+    #[must_use]
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Returns the offset added to the module base (or the absolute base address).
+    #[must_use]
+    pub fn base_offset(&self) -> i64 {
+        self.base_offset
+    }
+
+    /// Returns the offsets applied after each dereference.
+    #[must_use]
+    pub fn offsets(&self) -> &[i64] {
+        &self.offsets
+    }
+
+    /// Computes the base address of the path, looking up the module if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::ModuleNotFound`] if the module is not loaded in the target.
+    pub fn base<M: MemoryAccess + ?Sized>(&self, memory: &M) -> Result<usize, Errors> {
+        let module_base = match &self.module {
+            Some(name) => {
+                memory
+                    .find_module(name)
+                    .ok_or_else(|| Errors::ModuleNotFound(name.to_string()))?
+                    .module_addr
+            }
+            None => 0,
+        };
+        Ok(module_base.wrapping_add_signed(self.base_offset as isize))
+    }
+
+    /// Resolves the path to the address of the final value.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::ModuleNotFound`] if the module is not loaded in the target.
+    /// * [`Errors::BrokenPointerPath`] with the level whose address could not be
+    ///   dereferenced; level 0 is the base address.
+    pub fn resolve<M: MemoryAccess + ?Sized>(&self, memory: &M) -> Result<usize, Errors> {
+        let mut address = self.base(memory)?;
+
+        for (level, &offset) in self.offsets.iter().enumerate() {
+            address = read_pointer(memory, address)
+                .map_err(|err| broken(level, address, &err))?
+                .wrapping_add_signed(offset as isize);
+        }
+        Ok(address)
+    }
+
+    /// Resolves the path and reads the final value as `T`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`resolve`](Self::resolve); a failing read of the final value is
+    /// reported as level `offsets().len()`.
+    pub fn read<T: Pod, M: MemoryAccess + ?Sized>(&self, memory: &M) -> Result<T, Errors> {
+        let address = self.resolve(memory)?;
+        read_value(memory, address).map_err(|err| broken(self.offsets.len(), address, &err))
+    }
+}
+
+/// Formats the path in Cheat Engine arrow notation, e.g. `"hitman3.exe"+22BAF18 -> 18`.
+impl Display for PointerPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.module {
+            Some(module) => {
+                write!(f, "\"{module}\"")?;
+                write_signed(f, self.base_offset, true)?;
+            }
+            None => write_signed(f, self.base_offset, false)?,
+        }
+        for &offset in &self.offsets {
+            f.write_str(" -> ")?;
+            write_signed(f, offset, false)?;
+        }
+        Ok(())
+    }
+}
+
+/// An address in the target that points to a `T`.
+///
+/// `RemotePtr` gives bare addresses a type, so a `Vec3` cannot be read where a
+/// `Player*` lives without an explicit [`cast`](Self::cast). Arithmetic follows raw
+/// pointers: [`offset`](Self::offset) and [`index`](Self::index) count elements of
+/// `T`, [`byte_offset`](Self::byte_offset) counts bytes. Nothing is read before
+/// [`read`](Self::read) or [`deref`](Self::deref) is called.
+///
+/// As a [`Pod`] field of a `#[repr(C)]` struct it is `usize`-wide. In a
+/// `#[derive(RemoteStruct)]` struct, mark it `#[pointer]` to read it with the
+/// target's pointer width without following it.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::{RemotePtr, mock::MockProcess, types::Protection};
+///
+/// let process = MockProcess::new()
+///     .region(0x1000, 0x2000usize.to_ne_bytes(), Protection::RW)
+///     .region(0x2000, [0u8; 0x10], Protection::RW);
+/// process.poke(0x2008, &7u32.to_ne_bytes());
+///
+/// // u32* entities[]; entities[0][2]
+/// let entities = RemotePtr::<RemotePtr<u32>>::new(0x1000);
+/// let health = entities.deref(&process).unwrap().index(2);
+/// assert_eq!(health.address(), 0x2008);
+/// assert_eq!(health.read(&process), Ok(7));
+/// ```
+#[repr(transparent)]
+pub struct RemotePtr<T> {
+    address: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RemotePtr<T> {
+    /// Creates a pointer to the `T` at `address`.
+    #[must_use]
+    pub const fn new(address: usize) -> Self {
+        Self {
+            address,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a null pointer.
+    #[must_use]
+    pub const fn null() -> Self {
+        Self::new(0)
+    }
+
+    /// Returns the address the pointer points to.
+    #[must_use]
+    pub const fn address(self) -> usize {
+        self.address
+    }
+
+    /// Returns `true` if the address is 0.
+    #[must_use]
+    pub const fn is_null(self) -> bool {
+        self.address == 0
+    }
+
+    /// Reinterprets the pointer as pointing to a `U`, keeping the address.
+    #[must_use]
+    pub const fn cast<U>(self) -> RemotePtr<U> {
+        RemotePtr::new(self.address)
+    }
+
+    /// Moves the pointer by `count` elements of `T`, wrapping around like
+    /// `<*const T>::wrapping_offset`.
+    ///
+    /// Steps by the size of `T` in this process, so step through arrays of pointers
+    /// with [`offset_ptr`](RemotePtr::offset_ptr) instead.
+    #[must_use]
+    pub const fn offset(self, count: isize) -> Self {
+        self.byte_offset(count.wrapping_mul(size_of::<T>() as isize))
+    }
+
+    /// Moves the pointer by `bytes` bytes, e.g. to a field of a struct.
+    #[must_use]
+    pub const fn byte_offset(self, bytes: isize) -> Self {
+        Self::new(self.address.wrapping_add_signed(bytes))
+    }
+
+    /// Returns a pointer to the element at `index` of the array that starts at this
+    /// pointer, like `&ptr[index]` in C.
+    ///
+    /// Steps by the size of `T` in this process, so index arrays of pointers with
+    /// [`index_ptr`](RemotePtr::index_ptr) instead.
+    #[must_use]
+    pub const fn index(self, index: usize) -> Self {
+        Self::new(
+            self.address
+                .wrapping_add(index.wrapping_mul(size_of::<T>())),
+        )
+    }
+}
+
+impl<T: Pod> RemotePtr<T> {
+    /// Reads the `T` the pointer points to, see [`read_value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::ReadFailed`] if the value could not be read completely.
+    pub fn read<M: MemoryAccess + ?Sized>(self, memory: &M) -> Result<T, Errors> {
+        read_value(memory, self.address)
+    }
+
+    /// Reads `count` elements of the array that starts at this pointer, see
+    /// [`read_slice`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`read_slice`].
+    pub fn read_slice<M: MemoryAccess + ?Sized>(
+        self,
+        memory: &M,
+        count: usize,
+    ) -> Result<Vec<T>, Errors> {
+        read_slice(memory, self.address, count)
+    }
+
+    /// Writes `value` to the address the pointer points to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::WriteFailed`] if the value could not be written completely.
+    pub fn write<M: MemoryAccess + ?Sized>(self, memory: &M, value: &T) -> Result<(), Errors> {
+        try_write(memory, self.address, value)
+    }
+}
+
+impl<T: RemoteStruct> RemotePtr<T> {
+    /// Reads the [`RemoteStruct`] the pointer points to, see [`read_struct`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`read_struct`].
+    pub fn read_struct<M: MemoryAccess + ?Sized>(self, memory: &M) -> Result<T, Errors> {
+        read_struct(memory, self.address)
+    }
+}
+
+impl<T> RemotePtr<RemotePtr<T>> {
+    /// Reads the pointer this pointer points to, with the target's
+    /// [`pointer_width`](MemoryAccess::pointer_width), like `*ptr` for a `T**`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::ReadFailed`] if the pointer could not be read completely.
+    pub fn deref<M: MemoryAccess + ?Sized>(self, memory: &M) -> Result<RemotePtr<T>, Errors> {
+        read_pointer(memory, self.address).map(RemotePtr::new)
+    }
+
+    /// Moves the pointer by `count` pointers of the target's
+    /// [`pointer_width`](MemoryAccess::pointer_width), which [`offset`](Self::offset)
+    /// gets wrong for 32-bit targets.
+    #[must_use]
+    pub fn offset_ptr<M: MemoryAccess + ?Sized>(self, memory: &M, count: isize) -> Self {
+        self.byte_offset(count.wrapping_mul(memory.pointer_width() as isize))
+    }
+
+    /// Returns a pointer to the pointer at `index` of the array that starts at this
+    /// pointer, stepping by the target's [`pointer_width`](MemoryAccess::pointer_width)
+    /// like `&ptr[index]` for a `T**` in the target.
+    #[must_use]
+    pub fn index_ptr<M: MemoryAccess + ?Sized>(self, memory: &M, index: usize) -> Self {
+        Self::new(
+            self.address
+                .wrapping_add(index.wrapping_mul(memory.pointer_width())),
+        )
+    }
+}
+
+// Manual impls, since derives would require `T` to implement the traits as well.
+impl<T> Clone for RemotePtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RemotePtr<T> {}
+
+impl<T> PartialEq for RemotePtr<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
+impl<T> Eq for RemotePtr<T> {}
+
+impl<T> Hash for RemotePtr<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.address.hash(state);
+    }
+}
+
+impl<T> Default for RemotePtr<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T> std::fmt::Debug for RemotePtr<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RemotePtr<{}>({:#X})",
+            std::any::type_name::<T>(),
+            self.address
+        )
+    }
+}
+
+/// Formats the address like a pointer, e.g. `0x7FF6A0001000`.
+impl<T> Display for RemotePtr<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#X}", self.address)
+    }
+}
+
+impl<T> From<usize> for RemotePtr<T> {
+    fn from(address: usize) -> Self {
+        Self::new(address)
+    }
+}
+
+// SAFETY: `repr(transparent)` over a `usize`; an address is valid for any bit
+// pattern and is not dereferenced in the current process.
+unsafe impl<T: 'static> Pod for RemotePtr<T> {}
+
+fn write_signed(f: &mut std::fmt::Formatter<'_>, value: i64, with_plus: bool) -> std::fmt::Result {
+    match (value < 0, with_plus) {
+        (true, _) => write!(f, "-{:X}", value.unsigned_abs()),
+        (false, true) => write!(f, "+{value:X}"),
+        (false, false) => write!(f, "{value:X}"),
+    }
+}
+
+/// Converts a failed dereference into [`Errors::BrokenPointerPath`].
+fn broken(level: usize, address: usize, err: &Errors) -> Errors {
+    let (pid, os_error) = match err {
+        Errors::ReadFailed { pid, os_error, .. } => (*pid, *os_error),
+        _ => (None, None),
+    };
+    Errors::BrokenPointerPath {
+        pid,
+        level,
+        address,
+        os_error,
+    }
+}
+
+/// Splits `[[base]+a]+b` into `base` and `[a, b]`.
+fn parse_brackets(path: &str) -> Result<(&str, Vec<i64>), Errors> {
+    let depth = path.bytes().take_while(|&b| b == b'[').count();
+    let parts = split_unquoted(&path[depth..], "]");
+    let base = parts[0];
+
+    let offsets = parts[1..]
+        .iter()
+        .map(|part| match part.trim() {
+            "" => Ok(0),
+            offset => parse_signed(offset),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if offsets.len() != depth || split_unquoted(base, "[").len() > 1 {
+        return Err(Errors::InvalidPointerPath(path.to_string()));
+    }
+    Ok((base, offsets))
+}
+
+/// Parses `"module"+offset`, `module+offset-offset` or an absolute address.
+fn parse_base(base: &str) -> Result<(Option<&str>, i64), Errors> {
+    let mut module = None;
+    let mut offset = 0i64;
+
+    for (negative, term) in split_terms(base)? {
+        let term = term.trim();
+        let quoted = term.len() >= 2 && term.starts_with('"') && term.ends_with('"');
+
+        match parse_hex(term) {
+            Some(value) if !quoted => {
+                offset = if negative {
+                    offset.wrapping_sub(value)
+                } else {
+                    offset.wrapping_add(value)
+                };
+            }
+            _ if negative || module.is_some() || term.is_empty() => {
+                return Err(Errors::InvalidPointerPath(term.to_string()));
+            }
+            _ => module = Some(term.trim_matches('"')),
+        }
+    }
+    Ok((module, offset))
+}
+
+/// Splits `text` at every `separator` outside of quotes, so quoted module names may
+/// contain it.
+fn split_unquoted<'a>(text: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+
+    for (idx, c) in text.char_indices() {
+        if idx < start {
+            // Inside a multi-character separator
+            continue;
+        }
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes && text[idx..].starts_with(separator) {
+            parts.push(&text[start..idx]);
+            start = idx + separator.len();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Splits `base` at `+`/`-` outside of quotes, returning each term with its sign.
+fn split_terms(base: &str) -> Result<Vec<(bool, &str)>, Errors> {
+    let mut terms = Vec::new();
+    let mut start = 0;
+    let mut negative = false;
+    let mut in_quotes = false;
+
+    for (idx, c) in base.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '+' | '-' if !in_quotes => {
+                // Only a single leading sign may go without a term before it
+                if start > 0 || !base[start..idx].trim().is_empty() || !terms.is_empty() {
+                    terms.push((negative, &base[start..idx]));
+                }
+                negative = c == '-';
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_quotes {
+        return Err(Errors::InvalidPointerPath(base.to_string()));
+    }
+    terms.push((negative, &base[start..]));
+    Ok(terms)
+}
+
+/// Parses an offset with an optional sign, e.g. `18`, `+0x18` or `-10`.
+fn parse_signed(offset: &str) -> Result<i64, Errors> {
+    let trimmed = offset.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+
+    let value =
+        parse_hex(digits.trim()).ok_or_else(|| Errors::InvalidPointerPath(trimmed.to_string()))?;
+    Ok(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+/// Parses a hexadecimal number with an optional `0x` prefix.
+///
+/// Signs are handled by the callers, so a sign left in `digits` (which
+/// `from_str_radix` would accept) makes the number invalid.
+fn parse_hex(digits: &str) -> Option<i64> {
+    let digits = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+        .unwrap_or(digits);
+    if digits.starts_with(['+', '-']) {
+        return None;
+    }
+    u64::from_str_radix(digits, 16).ok().map(u64::cast_signed)
+}
@@ -0,0 +1,667 @@
+use std::{
+    collections::HashMap,
+    io,
+    sync::{OnceLock, mpsc},
+    thread,
+};
+
+#[cfg(windows)]
+use crate::close_handle;
+use crate::{
+    batch::{BatchRead, BatchResults},
+    errors::Errors,
+    memory::MemoryAccess,
+    options::{Access, OpenOptions},
+    pattern::Pattern,
+    platform::{self, RawHandle},
+    pod::Pod,
+    read, read_chain, read_into, read_slice, read_value,
+    remote::{RemoteStruct, read_struct},
+    resolve_chain,
+    strings::{
+        self, Decoding, Encoding, read_cstring, read_terminated_string, read_utf16_string,
+        write_cstring, write_utf16_string,
+    },
+    try_read, try_write,
+    types::{Architecture, MemoryRegion, ModuleData},
+    utils::{
+        SignatureMatches, find_signature, find_signature_iter, find_unique_signature,
+        process_modules, resolve_relative,
+    },
+    write,
+};
+
+/// An opened target process that owns its system handle.
+///
+/// `Process` is the value returned by [`find_process`](crate::find_process). It keeps
+/// the handle, the PID and the module map of the target together and exposes
+/// memory access as methods, so callers never have to pass a raw handle around.
+///
+/// # Resource Management
+///
+/// On Windows the handle is closed automatically when the `Process` is dropped,
+/// including on early returns and `?` propagation. There is no need to call
+/// `close_handle` manually. On Linux the pidfd that tracks the target is closed
+/// the same way.
+///
+/// # Access Rights
+///
+/// A `Process` remembers the [`OpenOptions`] it was opened with. Methods that need
+/// a right that was not requested fail with [`Errors::MissingAccess`] instead of
+/// reaching the OS; through [`MemoryAccess`] such reads and writes fail with
+/// [`io::ErrorKind::PermissionDenied`].
+///
+/// # Liveness
+///
+/// When an operation fails, the `Process` checks whether the target is still
+/// running. On Linux a successful read or write also costs one `poll` on a pidfd,
+/// as the PID of an exited target may already belong to a new process; on Windows
+/// the open handle keeps the PID from being reused. Once the target has exited the `Process` marks itself dead and all operations fail with
+/// [`Errors::ProcessExited`] (or [`io::ErrorKind::NotFound`] through
+/// [`MemoryAccess`]) instead of returning stale data. Use
+/// [`is_alive`](Self::is_alive) to check explicitly or
+/// [`watch_exit`](Self::watch_exit) to be notified without polling.
+#[derive(Debug)]
+pub struct Process {
+    handle: RawHandle,
+    id: u32,
+    options: OpenOptions,
+    architecture: Option<Architecture>,
+    module_list: HashMap<String, ModuleData>,
+    exited: OnceLock<Option<i32>>,
+}
+
+impl Process {
+    /// Opens the process with the given `pid` with every access right and collects
+    /// its loaded modules.
+    ///
+    /// Use [`OpenOptions`] to request only the rights a tool needs.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::ProcessNotFound`] if no process with this PID exists.
+    /// * [`Errors::AccessDenied`] if the OS refuses full access.
+    pub fn open(pid: u32) -> Result<Self, Errors> {
+        OpenOptions::all().open(pid)
+    }
+
+    /// Takes ownership of an already opened `handle` and detects the architecture of
+    /// the target, without enumerating modules.
+    pub(crate) fn from_handle(handle: RawHandle, id: u32, options: OpenOptions) -> Self {
+        Self {
+            architecture: platform::architecture(&handle, id),
+            handle,
+            id,
+            options,
+            module_list: HashMap::new(),
+            exited: OnceLock::new(),
+        }
+    }
+
+    /// Returns the process identifier (PID) of the target.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the options (and thereby the access rights) the process was opened with.
+    #[must_use]
+    pub fn options(&self) -> &OpenOptions {
+        &self.options
+    }
+
+    /// Returns the architecture of the target, or `None` if it could not be detected,
+    /// in which case pointers are assumed to be as wide as in the current process.
+    ///
+    /// A 32-bit game under WOW64 reports [`Architecture::X86`]; pointer chains, pointer
+    /// paths and expressions then dereference 4-byte pointers.
+    #[must_use]
+    pub fn architecture(&self) -> Option<Architecture> {
+        self.architecture
+    }
+
+    /// Returns `false` once the target has exited. Every call asks the OS while the
+    /// target runs; once it has exited the result is cached and never queried again.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        if self.exited.get().is_some() {
+            return false;
+        }
+        match platform::exit_code(&self.handle) {
+            Some(code) => {
+                let _ = self.exited.set(code);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Returns the exit code once the target has exited, if the OS reports it.
+    #[must_use]
+    pub fn exit_code(&self) -> Option<i32> {
+        if self.is_alive() {
+            None
+        } else {
+            self.exited.get().copied().flatten()
+        }
+    }
+
+    /// Spawns a thread that waits for the target to exit and sends its exit code
+    /// (if the OS reports it) through the returned channel.
+    ///
+    /// Intended for long-running tools that want to shut down or re-attach when the
+    /// game closes. The thread does not borrow the `Process` and ends with the target.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Errors::AccessDenied`] if the OS refuses a `SYNCHRONIZE` handle
+    /// for the waiting thread (Windows only).
+    pub fn watch_exit(&self) -> Result<mpsc::Receiver<Option<i32>>, Errors> {
+        let wait = platform::exit_waiter(&self.handle, self.id)?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(wait());
+        });
+        Ok(receiver)
+    }
+
+    /// Fails with [`Errors::MissingAccess`] unless the process was opened with `access`
+    /// and with [`Errors::ProcessExited`] if the target is already known to have
+    /// exited. Does not query the OS.
+    fn require(&self, access: Access) -> Result<(), Errors> {
+        if !self.options.allows(access) {
+            return Err(Errors::MissingAccess(access));
+        }
+        if self.exited.get().is_some() {
+            return Err(self.exited_error());
+        }
+        Ok(())
+    }
+
+    /// Replaces the error of a failed operation with [`Errors::ProcessExited`] if the
+    /// target has exited in the meantime. Does not query the OS, as the failed reads
+    /// and writes of the operation have already checked.
+    fn checked<T>(&self, result: Result<T, Errors>) -> Result<T, Errors> {
+        result.map_err(|err| {
+            if self.exited.get().is_some() {
+                self.exited_error()
+            } else {
+                err
+            }
+        })
+    }
+
+    fn exited_error(&self) -> Errors {
+        Errors::ProcessExited {
+            pid: self.id,
+            code: self.exited.get().copied().flatten(),
+        }
+    }
+
+    /// [`require`](Self::require) for the [`MemoryAccess`] methods.
+    fn require_io(&self, access: Access) -> io::Result<()> {
+        self.require(access).map_err(io_error)
+    }
+
+    /// Fails a read or write with [`Errors::ProcessExited`] if the target has exited,
+    /// which is checked when it failed or when it may have reached another process.
+    fn checked_io<T>(&self, result: io::Result<T>) -> io::Result<T> {
+        if (result.is_err() || platform::is_stale(&self.handle)) && !self.is_alive() {
+            return Err(io_error(self.exited_error()));
+        }
+        result
+    }
+
+    /// Looks up a loaded module by its name (case-insensitive), e.g. `"hitman3.exe"`.
+    #[must_use]
+    pub fn module(&self, name: &str) -> Option<&ModuleData> {
+        self.module_list.get(&name.to_ascii_lowercase())
+    }
+
+    /// Returns every loaded module keyed by its lowercase name.
+    #[must_use]
+    pub fn modules(&self) -> &HashMap<String, ModuleData> {
+        &self.module_list
+    }
+
+    /// Re-enumerates the modules of the target, picking up libraries loaded since
+    /// the process was opened. Without the `query` right the module list stays empty.
+    pub fn refresh_modules(&mut self) {
+        self.module_list = process_modules(self);
+    }
+
+    /// Performs a multi-level pointer traversal and reads the final value into `buffer`.
+    ///
+    /// See [`read`](crate::read) for the traversal logic and its caveats.
+    pub fn read<T: Pod>(&self, addr: usize, offsets: &[u32], buffer: &mut T) {
+        read(self, addr, offsets, buffer);
+    }
+
+    /// Writes `value` to `addr` in the target process.
+    ///
+    /// See [`write`](crate::write) for details.
+    pub fn write<T: Pod>(&self, addr: usize, value: &T) {
+        write(self, addr, value);
+    }
+
+    /// Fallible variant of [`read`](Self::read), see [`try_read`](crate::try_read).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] for the first hop that could not be read.
+    pub fn try_read<T: Pod>(
+        &self,
+        addr: usize,
+        offsets: &[u32],
+        buffer: &mut T,
+    ) -> Result<(), Errors> {
+        self.require(Access::Read)?;
+        self.checked(try_read(self, addr, offsets, buffer))
+    }
+
+    /// Fallible variant of [`write`](Self::write), see [`try_write`](crate::try_write).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `write`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::WriteFailed`] if the value could not be written completely.
+    pub fn try_write<T: Pod>(&self, addr: usize, value: &T) -> Result<(), Errors> {
+        self.require(Access::Write)?;
+        self.checked(try_write(self, addr, value))
+    }
+
+    /// Reads a value of type `T` at `addr`, see [`read_value`](crate::read_value).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] if the value could not be read completely.
+    pub fn read_value<T: Pod>(&self, addr: usize) -> Result<T, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_value(self, addr))
+    }
+
+    /// Reads `count` consecutive values of type `T` at `addr` with a single read, see
+    /// [`read_slice`](crate::read_slice).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::SliceTooLarge`], [`Errors::UnreadableElements`] or
+    ///   [`Errors::ReadFailed`] as described for [`read_slice`](crate::read_slice).
+    pub fn read_slice<T: Pod>(&self, addr: usize, count: usize) -> Result<Vec<T>, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_slice(self, addr, count))
+    }
+
+    /// Fills `values` with consecutive values of type `T` at `addr`, see
+    /// [`read_into`](crate::read_into).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::UnreadableElements`] or [`Errors::ReadFailed`] as described for
+    ///   [`read_into`](crate::read_into).
+    pub fn read_into<T: Pod>(&self, addr: usize, values: &mut [T]) -> Result<(), Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_into(self, addr, values))
+    }
+
+    /// Reads the [`RemoteStruct`] `T` at `addr` with one bulk read, see [`read_struct`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] if the struct or one behind a pointer field could not
+    ///   be read completely.
+    pub fn read_struct<T: RemoteStruct>(&self, addr: usize) -> Result<T, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_struct(self, addr))
+    }
+
+    /// Executes every request of `batch` with as few OS calls as possible, see
+    /// [`BatchRead`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    ///
+    /// Failures of single requests are reported by [`BatchResults`].
+    pub fn read_batch(&self, batch: &BatchRead) -> Result<BatchResults, Errors> {
+        self.require(Access::Read)?;
+        let results = batch.read(self);
+        // Failed requests have already checked whether the target exited
+        self.require(Access::Read)?;
+        Ok(results)
+    }
+
+    /// Returns the address a pointer chain leads to, see [`resolve_chain`](crate::resolve_chain).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] for the first hop that could not be read.
+    pub fn resolve_chain(&self, base: usize, offsets: &[u32]) -> Result<usize, Errors> {
+        self.require(Access::Read)?;
+        self.checked(resolve_chain(self, base, offsets))
+    }
+
+    /// Reads the value a pointer chain leads to, see [`read_chain`](crate::read_chain).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] for the first hop that could not be read.
+    pub fn read_chain<T: Pod>(&self, base: usize, offsets: &[u32]) -> Result<T, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_chain(self, base, offsets))
+    }
+
+    /// Reads a null-terminated UTF-8 string of at most `max_len` bytes including the
+    /// terminator, see [`read_cstring`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`], [`Errors::UnterminatedString`] or
+    ///   [`Errors::InvalidString`] as described for [`read_cstring`].
+    pub fn read_cstring(&self, addr: usize, max_len: usize) -> Result<String, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_cstring(self, addr, max_len))
+    }
+
+    /// Reads a null-terminated UTF-16LE string of at most `max_len` code units
+    /// including the terminator, see [`read_utf16_string`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`], [`Errors::UnterminatedString`] or
+    ///   [`Errors::InvalidString`] as described for [`read_utf16_string`].
+    pub fn read_utf16_string(&self, addr: usize, max_len: usize) -> Result<String, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_utf16_string(self, addr, max_len))
+    }
+
+    /// Reads a null-terminated string of any encoding, see [`read_terminated_string`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`], [`Errors::UnterminatedString`] or
+    ///   [`Errors::InvalidString`] as described for [`read_terminated_string`].
+    pub fn read_terminated_string(
+        &self,
+        addr: usize,
+        max_len: usize,
+        encoding: Encoding,
+        decoding: Decoding,
+    ) -> Result<String, Errors> {
+        self.require(Access::Read)?;
+        self.checked(read_terminated_string(
+            self, addr, max_len, encoding, decoding,
+        ))
+    }
+
+    /// Reads a string from a fixed-length buffer of `len` code units, see
+    /// [`strings::read_string`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] or [`Errors::InvalidString`] as described for
+    ///   [`strings::read_string`].
+    pub fn read_string(
+        &self,
+        addr: usize,
+        len: usize,
+        encoding: Encoding,
+        decoding: Decoding,
+    ) -> Result<String, Errors> {
+        self.require(Access::Read)?;
+        self.checked(strings::read_string(self, addr, len, encoding, decoding))
+    }
+
+    /// Writes a null-terminated UTF-8 string into a buffer of `max_len` bytes, see
+    /// [`write_cstring`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `write`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::StringTooLong`] or [`Errors::WriteFailed`] as described for
+    ///   [`strings::write_string`].
+    pub fn write_cstring(&self, addr: usize, value: &str, max_len: usize) -> Result<(), Errors> {
+        self.require(Access::Write)?;
+        self.checked(write_cstring(self, addr, value, max_len))
+    }
+
+    /// Writes a null-terminated UTF-16LE string into a buffer of `max_len` code
+    /// units, see [`write_utf16_string`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `write`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::StringTooLong`] or [`Errors::WriteFailed`] as described for
+    ///   [`strings::write_string`].
+    pub fn write_utf16_string(
+        &self,
+        addr: usize,
+        value: &str,
+        max_len: usize,
+    ) -> Result<(), Errors> {
+        self.require(Access::Write)?;
+        self.checked(write_utf16_string(self, addr, value, max_len))
+    }
+
+    /// Writes a null-terminated string of any encoding into a buffer of `max_len`
+    /// code units, see [`strings::write_string`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `write`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::StringTooLong`], [`Errors::InvalidString`] or
+    ///   [`Errors::WriteFailed`] as described for [`strings::write_string`].
+    pub fn write_string(
+        &self,
+        addr: usize,
+        value: &str,
+        encoding: Encoding,
+        max_len: usize,
+    ) -> Result<(), Errors> {
+        self.require(Access::Write)?;
+        self.checked(strings::write_string(self, addr, value, encoding, max_len))
+    }
+
+    /// Resolves a relative instruction operand, see [`resolve_relative`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::ReadFailed`] if the displacement could not be read.
+    pub fn resolve_relative(
+        &self,
+        instruction: usize,
+        displacement_offset: usize,
+        instruction_len: usize,
+    ) -> Result<usize, Errors> {
+        self.require(Access::Read)?;
+        self.checked(resolve_relative(
+            self,
+            instruction,
+            displacement_offset,
+            instruction_len,
+        ))
+    }
+
+    /// Searches `size` bytes starting at `base` for `pattern`, see [`find_signature`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read` or `query`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::SignatureNotFound`] if the pattern is not present in the range.
+    pub fn find_signature(
+        &self,
+        base: usize,
+        size: usize,
+        pattern: &Pattern,
+    ) -> Result<usize, Errors> {
+        self.require(Access::Read)?;
+        self.require(Access::Query)?;
+        self.checked(find_signature(self, base, size, pattern))
+    }
+
+    /// Returns every match of `pattern` in the range, see
+    /// [`find_signature_all`](crate::utils::find_signature_all).
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read` or `query`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    pub fn find_signature_all(
+        &self,
+        base: usize,
+        size: usize,
+        pattern: &Pattern,
+    ) -> Result<Vec<usize>, Errors> {
+        let matches = self.find_signature_iter(base, size, pattern)?.collect();
+        // Failed reads have already checked whether the target exited
+        self.require(Access::Read)?;
+        Ok(matches)
+    }
+
+    /// Returns a lazy iterator over the matches of `pattern` in the range, see
+    /// [`find_signature_iter`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read` or `query`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    pub fn find_signature_iter<'a>(
+        &'a self,
+        base: usize,
+        size: usize,
+        pattern: &'a Pattern,
+    ) -> Result<SignatureMatches<'a, Self>, Errors> {
+        self.require(Access::Read)?;
+        self.require(Access::Query)?;
+        Ok(find_signature_iter(self, base, size, pattern))
+    }
+
+    /// Searches the range for a `pattern` that must match exactly once, see
+    /// [`find_unique_signature`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::MissingAccess`] if the process was opened without `read` or `query`.
+    /// * [`Errors::ProcessExited`] if the target has exited.
+    /// * [`Errors::SignatureNotFound`] if the pattern is not present in the range.
+    /// * [`Errors::AmbiguousSignature`] if it matches more than once.
+    pub fn find_unique_signature(
+        &self,
+        base: usize,
+        size: usize,
+        pattern: &Pattern,
+    ) -> Result<usize, Errors> {
+        self.require(Access::Read)?;
+        self.require(Access::Query)?;
+        self.checked(find_unique_signature(self, base, size, pattern))
+    }
+}
+
+/// Delegates to the platform backend of the owned handle, refusing operations the
+/// process was not opened for or that target an exited process.
+impl MemoryAccess for Process {
+    fn read_bytes(&self, addr: usize, buffer: &mut [u8]) -> io::Result<usize> {
+        self.require_io(Access::Read)?;
+        self.checked_io(self.handle.read_bytes(addr, buffer))
+    }
+
+    fn write_bytes(&self, addr: usize, buffer: &[u8]) -> io::Result<usize> {
+        self.require_io(Access::Write)?;
+        self.checked_io(self.handle.write_bytes(addr, buffer))
+    }
+
+    fn read_scattered(&self, requests: &mut [(usize, &mut [u8])]) -> Vec<io::Result<usize>> {
+        if self.require_io(Access::Read).is_ok() {
+            let results = self.handle.read_scattered(requests);
+            let failed = !results.iter().all(Result::is_ok);
+            if !(failed || platform::is_stale(&self.handle)) || self.is_alive() {
+                return results;
+            }
+        }
+        requests
+            .iter()
+            .map(|_| self.require_io(Access::Read).map(|()| 0))
+            .collect()
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        if self.require(Access::Query).is_err() {
+            return Vec::new();
+        }
+        self.handle.regions()
+    }
+
+    fn modules(&self) -> Vec<ModuleData> {
+        if self.require(Access::Query).is_err() {
+            return Vec::new();
+        }
+        self.handle.modules()
+    }
+
+    fn find_module(&self, name: &str) -> Option<ModuleData> {
+        self.module(name).cloned()
+    }
+
+    fn pid(&self) -> Option<u32> {
+        Some(self.id)
+    }
+
+    fn architecture(&self) -> Option<Architecture> {
+        self.architecture
+    }
+}
+
+/// Converts the errors of [`Process::require`] for the [`MemoryAccess`] methods.
+fn io_error(err: Errors) -> io::Error {
+    let kind = match err {
+        Errors::MissingAccess(_) => io::ErrorKind::PermissionDenied,
+        _ => io::ErrorKind::NotFound,
+    };
+    io::Error::new(kind, err.to_string())
+}
+
+/// Closes the owned handle when the `Process` goes out of scope.
+#[cfg(windows)]
+impl Drop for Process {
+    fn drop(&mut self) {
+        close_handle(self.handle);
+    }
+}
+
+/// Closes the pidfd of the owned handle when the `Process` goes out of scope.
+#[cfg(target_os = "linux")]
+impl Drop for Process {
+    fn drop(&mut self) {
+        platform::close_pidfd(self.handle);
+    }
+}
@@ -0,0 +1,206 @@
+use std::ptr;
+
+use crate::{errors::Errors, memory::MemoryAccess, pod::Pod, pointer::RemotePtr};
+
+/// A mirror of a class in the target, usually implemented with
+/// `#[derive(RemoteStruct)]`.
+///
+/// The whole struct is fetched with a single read of
+/// [`remote_size`](Self::remote_size) bytes and every field is decoded from that
+/// buffer at its offset. Fields marked `#[pointer]` hold a pointer in the target,
+/// which is followed with another read of the field type.
+///
+/// At most [`MAX_POINTER_DEPTH`] pointers are followed in a row, so cyclic data in
+/// the target, such as two players targeting each other, fails with
+/// [`Errors::PointerDepthExceeded`] instead of overflowing the stack. Use a
+/// [`RemotePtr`] field to navigate such links lazily.
+///
+/// # Derive
+///
+/// Every field needs an `#[offset(..)]` attribute. Plain fields must be [`Pod`].
+/// Pointer fields must be a `RemoteStruct` themselves, an `Option` of one, which
+/// is `None` for a null pointer, or a [`RemotePtr`] that is not followed until it
+/// is read. Pointers have the
+/// [`pointer_width`](MemoryAccess::pointer_width) of the target.
+///
+/// ```
+/// use gamehack_librs::{RemoteStruct, mock::MockProcess, remote::read_struct, types::Protection};
+///
+/// #[derive(Debug, PartialEq, RemoteStruct)]
+/// struct Weapon {
+///     #[offset(0x8)]
+///     ammo: u32,
+/// }
+///
+/// #[derive(Debug, PartialEq, RemoteStruct)]
+/// struct Player {
+///     #[offset(0x18)]
+///     health: f32,
+///     #[offset(0x20)]
+///     #[pointer]
+///     weapon: Option<Weapon>,
+/// }
+///
+/// let process = MockProcess::new()
+///     .region(0x1000, [0u8; 0x28], Protection::RW)
+///     .region(0x2000, [0u8; 0x10], Protection::RW);
+/// process.poke(0x1018, &100f32.to_ne_bytes());
+/// process.poke(0x1020, &0x2000usize.to_ne_bytes());
+/// process.poke(0x2008, &30u32.to_ne_bytes());
+///
+/// let player: Player = read_struct(&process, 0x1000).unwrap();
+/// assert_eq!(player, Player { health: 100.0, weapon: Some(Weapon { ammo: 30 }) });
+/// ```
+pub trait RemoteStruct: Sized {
+    /// Returns the number of bytes read in one go: the end of the last field for a
+    /// target with pointers of `pointer_width` bytes.
+    fn remote_size(pointer_width: usize) -> usize;
+
+    /// Decodes the struct from `bytes`, the first [`remote_size`](Self::remote_size)
+    /// bytes at its address, following pointer fields through `memory`. `depth` is
+    /// the number of pointers followed to reach the struct and is passed on to
+    /// [`FollowPointer::follow`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first pointer field that could not be followed.
+    fn decode<M: MemoryAccess + ?Sized>(
+        memory: &M,
+        bytes: &[u8],
+        depth: usize,
+    ) -> Result<Self, Errors>;
+}
+
+/// Allows recursive layouts such as a player whose target is another player. The
+/// recursion ends at a null pointer (with `Option`) or after
+/// [`MAX_POINTER_DEPTH`] pointers.
+impl<T: RemoteStruct> RemoteStruct for Box<T> {
+    fn remote_size(pointer_width: usize) -> usize {
+        T::remote_size(pointer_width)
+    }
+
+    fn decode<M: MemoryAccess + ?Sized>(
+        memory: &M,
+        bytes: &[u8],
+        depth: usize,
+    ) -> Result<Self, Errors> {
+        T::decode(memory, bytes, depth).map(Box::new)
+    }
+}
+
+/// The number of pointers [`read_struct`] follows in a row before it gives up with
+/// [`Errors::PointerDepthExceeded`].
+pub const MAX_POINTER_DEPTH: usize = 64;
+
+/// Reads the [`RemoteStruct`] `T` at `addr` with a single read of its fields,
+/// followed by one read per pointer field.
+///
+/// # Errors
+///
+/// * [`Errors::ReadFailed`] if the struct or a struct behind one of its pointer
+///   fields could not be read completely.
+/// * [`Errors::PointerDepthExceeded`] if more than [`MAX_POINTER_DEPTH`] pointers
+///   had to be followed in a row, e.g. because the pointers form a cycle.
+pub fn read_struct<M, T>(memory: &M, addr: usize) -> Result<T, Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: RemoteStruct,
+{
+    read_struct_at(memory, addr, 0)
+}
+
+/// Reads the struct at `addr`, reached through `depth` pointers.
+fn read_struct_at<M, T>(memory: &M, addr: usize, depth: usize) -> Result<T, Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: RemoteStruct,
+{
+    if depth > MAX_POINTER_DEPTH {
+        return Err(Errors::PointerDepthExceeded {
+            address: addr,
+            max_depth: MAX_POINTER_DEPTH,
+        });
+    }
+    let mut bytes = vec![0u8; T::remote_size(memory.pointer_width())];
+    memory.read_exact(addr, &mut bytes)?;
+    T::decode(memory, &bytes, depth)
+}
+
+/// Types a `#[pointer]` field can have: the pointed-to struct, an `Option` of it
+/// that is `None` for a null pointer, or a lazy [`RemotePtr`].
+pub trait FollowPointer: Sized {
+    /// Reads the value `pointer` points to, the `depth`-th pointer followed in a
+    /// row.
+    ///
+    /// # Errors
+    ///
+    /// * [`Errors::ReadFailed`] if the value could not be read completely.
+    /// * [`Errors::PointerDepthExceeded`] if `depth` exceeds [`MAX_POINTER_DEPTH`].
+    fn follow<M: MemoryAccess + ?Sized>(
+        memory: &M,
+        pointer: usize,
+        depth: usize,
+    ) -> Result<Self, Errors>;
+}
+
+impl<T: RemoteStruct> FollowPointer for T {
+    fn follow<M: MemoryAccess + ?Sized>(
+        memory: &M,
+        pointer: usize,
+        depth: usize,
+    ) -> Result<Self, Errors> {
+        read_struct_at(memory, pointer, depth)
+    }
+}
+
+impl<T: RemoteStruct> FollowPointer for Option<T> {
+    fn follow<M: MemoryAccess + ?Sized>(
+        memory: &M,
+        pointer: usize,
+        depth: usize,
+    ) -> Result<Self, Errors> {
+        match pointer {
+            0 => Ok(None),
+            pointer => read_struct_at(memory, pointer, depth).map(Some),
+        }
+    }
+}
+
+/// Reads the pointer without following it, so pointer fields can be navigated
+/// lazily.
+impl<T> FollowPointer for RemotePtr<T> {
+    fn follow<M: MemoryAccess + ?Sized>(
+        _memory: &M,
+        pointer: usize,
+        _depth: usize,
+    ) -> Result<Self, Errors> {
+        Ok(RemotePtr::new(pointer))
+    }
+}
+
+/// Decodes the plain field at `offset`; used by `#[derive(RemoteStruct)]`.
+#[doc(hidden)]
+#[must_use]
+pub fn field<T: Pod>(bytes: &[u8], offset: usize) -> T {
+    let bytes = &bytes[offset..offset + size_of::<T>()];
+    unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<T>()) }
+}
+
+/// Follows the pointer field at `offset` of a struct reached through `depth`
+/// pointers; used by `#[derive(RemoteStruct)]`.
+#[doc(hidden)]
+pub fn pointer_field<M, T>(
+    memory: &M,
+    bytes: &[u8],
+    offset: usize,
+    depth: usize,
+) -> Result<T, Errors>
+where
+    M: MemoryAccess + ?Sized,
+    T: FollowPointer,
+{
+    let mut pointer = [0u8; 8];
+    let width = memory.pointer_width();
+    pointer[..width].copy_from_slice(&bytes[offset..offset + width]);
+    T::follow(memory, u64::from_le_bytes(pointer) as usize, depth + 1)
+}
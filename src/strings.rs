@@ -0,0 +1,276 @@
+use std::fmt::Display;
+
+use crate::{
+    errors::Errors,
+    memory::{MemoryAccess, PAGE_SIZE},
+};
+
+/// The character encoding of a string in the target's memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// UTF-8, which includes plain ASCII. One code unit is one byte.
+    Utf8,
+    /// Little-endian UTF-16 as used by `wchar_t` strings on Windows. One code unit
+    /// is two bytes.
+    Utf16Le,
+    /// ISO 8859-1, where every byte is the character with the same code point. Writes
+    /// fail for characters above `U+00FF`.
+    Latin1,
+}
+
+impl Encoding {
+    /// Returns the size of one code unit, and thus of the terminator, in bytes.
+    #[must_use]
+    pub fn unit_size(self) -> usize {
+        match self {
+            Encoding::Utf8 | Encoding::Latin1 => 1,
+            Encoding::Utf16Le => 2,
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Latin1 => "Latin-1",
+        })
+    }
+}
+
+/// How invalid sequences are handled when decoding a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decoding {
+    /// Fail with [`Errors::InvalidString`].
+    Strict,
+    /// Replace every invalid sequence with `U+FFFD`.
+    Lossy,
+}
+
+/// Reads a null-terminated UTF-8 string at `addr`, e.g. a `char name[32]` field.
+///
+/// At most `max_len` bytes including the terminator are read page by page, and never
+/// more than needed: the memory after the terminator does not have to be readable.
+///
+/// # Errors
+///
+/// * [`Errors::ReadFailed`] if the memory before the terminator is not readable.
+/// * [`Errors::UnterminatedString`] if the first `max_len` bytes contain no terminator.
+/// * [`Errors::InvalidString`] if the string is not valid UTF-8. Use
+///   [`read_terminated_string`] with [`Decoding::Lossy`] to accept it anyway.
+pub fn read_cstring<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    max_len: usize,
+) -> Result<String, Errors> {
+    read_terminated_string(memory, addr, max_len, Encoding::Utf8, Decoding::Strict)
+}
+
+/// Reads a null-terminated UTF-16LE string at `addr`, e.g. a `wchar_t name[32]` field.
+///
+/// `max_len` counts UTF-16 code units including the terminator, so it matches the
+/// array length of the field.
+///
+/// # Errors
+///
+/// * [`Errors::ReadFailed`] if the memory before the terminator is not readable.
+/// * [`Errors::UnterminatedString`] if the first `max_len` code units contain no
+///   terminator.
+/// * [`Errors::InvalidString`] if the string contains unpaired surrogates.
+pub fn read_utf16_string<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    max_len: usize,
+) -> Result<String, Errors> {
+    read_terminated_string(memory, addr, max_len, Encoding::Utf16Le, Decoding::Strict)
+}
+
+/// Reads a null-terminated string of any [`Encoding`] at `addr`.
+///
+/// # Arguments
+///
+/// * `memory` - Any [`MemoryAccess`] backend of the target process with read access.
+/// * `addr` - The address of the first character.
+/// * `max_len` - The maximum number of code units to read, including the terminator.
+/// * `encoding` - The encoding of the string, which also determines the terminator
+///   (one zero byte for UTF-8 and Latin-1, two for UTF-16).
+/// * `decoding` - Whether invalid sequences fail or are replaced.
+///
+/// # Errors
+///
+/// * [`Errors::ReadFailed`] if the memory before the terminator is not readable.
+/// * [`Errors::UnterminatedString`] if the first `max_len` code units contain no
+///   terminator.
+/// * [`Errors::InvalidString`] if the string is invalid and `decoding` is
+///   [`Decoding::Strict`].
+pub fn read_terminated_string<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    max_len: usize,
+    encoding: Encoding,
+    decoding: Decoding,
+) -> Result<String, Errors> {
+    let unit = encoding.unit_size();
+    let capacity = max_len.saturating_mul(unit);
+    let mut bytes = Vec::new();
+
+    while bytes.len() < capacity {
+        let start = bytes.len();
+        let current = addr.wrapping_add(start);
+        let chunk = (PAGE_SIZE - current % PAGE_SIZE).min(capacity - start);
+        bytes.resize(start + chunk, 0);
+        memory.read_exact(current, &mut bytes[start..])?;
+
+        // A code unit may straddle two chunks, so resume at the last complete one
+        let resume = start - start % unit;
+        if let Some(end) = bytes[resume..]
+            .chunks_exact(unit)
+            .position(|code_unit| code_unit.iter().all(|&byte| byte == 0))
+        {
+            bytes.truncate(resume + end * unit);
+            return decode(&bytes, addr, encoding, decoding);
+        }
+    }
+    Err(Errors::UnterminatedString {
+        address: addr,
+        max_len,
+    })
+}
+
+/// Reads a string stored in a fixed-length buffer of `len` code units at `addr`.
+///
+/// The whole buffer is read at once. The string ends at the first terminator, or
+/// fills the buffer if there is none, as is common for fixed-size name fields.
+///
+/// # Errors
+///
+/// * [`Errors::ReadFailed`] if the buffer could not be read completely.
+/// * [`Errors::InvalidString`] if the string is invalid and `decoding` is
+///   [`Decoding::Strict`].
+pub fn read_string<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    len: usize,
+    encoding: Encoding,
+    decoding: Decoding,
+) -> Result<String, Errors> {
+    let unit = encoding.unit_size();
+    let mut bytes = vec![0u8; len.saturating_mul(unit)];
+    memory.read_exact(addr, &mut bytes)?;
+
+    if let Some(end) = bytes
+        .chunks_exact(unit)
+        .position(|code_unit| code_unit.iter().all(|&byte| byte == 0))
+    {
+        bytes.truncate(end * unit);
+    }
+    decode(&bytes, addr, encoding, decoding)
+}
+
+/// Writes `value` as a null-terminated UTF-8 string to `addr`.
+///
+/// # Errors
+///
+/// See [`write_string`].
+pub fn write_cstring<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    value: &str,
+    max_len: usize,
+) -> Result<(), Errors> {
+    write_string(memory, addr, value, Encoding::Utf8, max_len)
+}
+
+/// Writes `value` as a null-terminated UTF-16LE string to `addr`.
+///
+/// # Errors
+///
+/// See [`write_string`].
+pub fn write_utf16_string<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    value: &str,
+    max_len: usize,
+) -> Result<(), Errors> {
+    write_string(memory, addr, value, Encoding::Utf16Le, max_len)
+}
+
+/// Writes `value` followed by a terminator to `addr`, never exceeding the buffer
+/// of `max_len` code units the target allocated for it.
+///
+/// Nothing is written if the encoded string does not fit. Bytes of the buffer after
+/// the terminator are left untouched.
+///
+/// # Arguments
+///
+/// * `memory` - Any [`MemoryAccess`] backend of the target process with write access.
+/// * `addr` - The address of the target's buffer.
+/// * `value` - The string to write. An embedded `'\0'` ends it early for the target.
+/// * `encoding` - The encoding the target expects.
+/// * `max_len` - The size of the target's buffer in code units, e.g. 32 for
+///   `char name[32]` as well as for `wchar_t name[32]`.
+///
+/// # Errors
+///
+/// * [`Errors::StringTooLong`] if `value` and the terminator need more than
+///   `max_len` code units.
+/// * [`Errors::InvalidString`] if `value` cannot be represented in `encoding`.
+/// * [`Errors::WriteFailed`] if the string could not be written completely.
+pub fn write_string<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    addr: usize,
+    value: &str,
+    encoding: Encoding,
+    max_len: usize,
+) -> Result<(), Errors> {
+    let mut bytes = encode(value, addr, encoding)?;
+    bytes.resize(bytes.len() + encoding.unit_size(), 0);
+
+    let len = bytes.len() / encoding.unit_size();
+    if len > max_len {
+        return Err(Errors::StringTooLong { len, max_len });
+    }
+    memory.write_all(addr, &bytes)
+}
+
+/// Decodes the string read from `address`, which is only used for errors.
+fn decode(
+    bytes: &[u8],
+    address: usize,
+    encoding: Encoding,
+    decoding: Decoding,
+) -> Result<String, Errors> {
+    let invalid = || Errors::InvalidString { address, encoding };
+
+    match (encoding, decoding) {
+        (Encoding::Utf8, Decoding::Strict) => {
+            String::from_utf8(bytes.to_vec()).map_err(|_| invalid())
+        }
+        (Encoding::Utf8, Decoding::Lossy) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        (Encoding::Utf16Le, _) => {
+            let units = bytes
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+            char::decode_utf16(units)
+                .map(|decoded| match decoding {
+                    Decoding::Strict => decoded.map_err(|_| invalid()),
+                    Decoding::Lossy => Ok(decoded.unwrap_or(char::REPLACEMENT_CHARACTER)),
+                })
+                .collect()
+        }
+        (Encoding::Latin1, _) => Ok(bytes.iter().map(|&byte| char::from(byte)).collect()),
+    }
+}
+
+/// Encodes the string to be written to `address`, which is only used for errors.
+fn encode(value: &str, address: usize, encoding: Encoding) -> Result<Vec<u8>, Errors> {
+    match encoding {
+        Encoding::Utf8 => Ok(value.as_bytes().to_vec()),
+        Encoding::Utf16Le => Ok(value.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        Encoding::Latin1 => value
+            .chars()
+            .map(|c| u8::try_from(c).map_err(|_| Errors::InvalidString { address, encoding }))
+            .collect(),
+    }
+}
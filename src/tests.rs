@@ -1,15 +1,1518 @@
-#[cfg(test)]
-mod tests {
-    use crate::find_process;
+use std::{cell::Cell, io, sync::atomic::AtomicBool, time::Duration};
+
+use crate::{
+    Errors, MemoryAccess, find_process,
+    mock::MockProcess,
+    types::{MemoryRegion, ModuleData, Protection},
+    wait_for_process, wait_for_process_cancellable,
+};
+
+/// A mock target with a zeroed, read-write region at every `(base, size)`.
+fn zeroed(regions: &[(usize, usize)]) -> MockProcess {
+    regions
+        .iter()
+        .fold(MockProcess::new(), |process, &(base, size)| {
+            process.region(base, vec![0u8; size], Protection::RW)
+        })
+}
+
+/// Counts the `read_bytes` calls the default `read_scattered` makes.
+struct Counting {
+    process: MockProcess,
+    reads: Cell<usize>,
+}
+
+impl Counting {
+    fn new(process: MockProcess) -> Self {
+        Self {
+            process,
+            reads: Cell::new(0),
+        }
+    }
+}
+
+impl MemoryAccess for Counting {
+    fn read_bytes(&self, addr: usize, buffer: &mut [u8]) -> io::Result<usize> {
+        self.reads.set(self.reads.get() + 1);
+        self.process.read_bytes(addr, buffer)
+    }
+
+    fn write_bytes(&self, addr: usize, buffer: &[u8]) -> io::Result<usize> {
+        self.process.write_bytes(addr, buffer)
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        self.process.regions()
+    }
+
+    fn modules(&self) -> Vec<ModuleData> {
+        self.process.modules()
+    }
+}
+
+#[cfg(windows)]
+#[test]
+fn found_process() {
+    assert!(find_process("svchost.exe").is_ok())
+}
+
+#[test]
+fn not_found_process() {
+    assert_eq!(find_process("").err().unwrap(), Errors::ProcessNotFound)
+}
+
+#[test]
+fn waiting_times_out() {
+    let err = wait_for_process("no such process", Duration::from_millis(150)).unwrap_err();
+    assert_eq!(err, Errors::Timeout);
+}
+
+#[test]
+fn waiting_can_be_cancelled() {
+    let cancel = AtomicBool::new(true);
+    let err = wait_for_process_cancellable("no such process", Duration::MAX, &cancel).unwrap_err();
+    assert_eq!(err, Errors::Cancelled);
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{error::Error, hint::black_box, io, time::Duration};
+
+    use crate::{
+        Errors, MemoryAccess, OpenOptions, OsError, Pattern, Process, find_process, find_processes,
+        linux::{
+            close_pidfd, elf_architecture, is_stale, modules_from_maps, open_process, parse_maps,
+        },
+        options::Access,
+        processes, read,
+        types::{Architecture, Protection},
+        wait_for_process, write,
+    };
+
+    const MAPS: &str = "\
+55d41f5d0000-55d41f5d2000 r--p 00000000 fe:00 317563                     /usr/bin/cat
+55d41f5d2000-55d41f5d7000 r-xp 00002000 fe:00 317563                     /usr/bin/cat
+55d43e958000-55d43e979000 rw-p 00000000 00:00 0                          [heap]
+7efc59459000-7efc5947e000 rw-p 00000000 00:00 0 
+7efc5947e000-7efc594a4000 r--p 00000000 fe:00 395379                     /usr/lib/libc.so.6
+";
+
+    static SIGNATURE: [u8; 12] = *b"\x13\x37gamehack\xC0\xDE";
+
+    fn current_process() -> Process {
+        Process::open(std::process::id()).unwrap()
+    }
+
+    #[test]
+    fn parses_maps() {
+        let regions = parse_maps(MAPS);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[1].0.base, 0x55d4_1f5d_2000);
+        assert_eq!(regions[1].0.size, 0x5000);
+        assert_eq!(regions[1].0.protection, Protection::RX);
+        assert_eq!(regions[2].1, Some("[heap]"));
+        assert_eq!(regions[3].1, None);
+    }
+
+    #[test]
+    fn groups_modules_by_file() {
+        let modules = modules_from_maps(MAPS);
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].module_name, "cat");
+        assert_eq!(modules[0].module_addr, 0x55d4_1f5d_0000);
+        assert_eq!(modules[0].module_size, 0x7000);
+        assert_eq!(modules[1].module_name, "libc.so.6");
+    }
+
+    #[test]
+    fn finds_own_process() {
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_str().unwrap();
+        let process = find_process(name).unwrap();
+        assert!(process.module(name).is_some());
+    }
+
+    #[test]
+    fn waits_for_running_process() {
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_str().unwrap();
+        let process = wait_for_process(name, Duration::ZERO).unwrap();
+        assert!(process.module(name).is_some());
+    }
+
+    #[test]
+    fn detects_process_exit() {
+        use std::{
+            io::Write,
+            process::{Command, Stdio},
+        };
+
+        let mut child = Command::new("sh")
+            .args(["-c", "read line; exit 3"])
+            .stdin(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let process = Process::open(child.id()).unwrap();
+        let exited = process.watch_exit().unwrap();
+        assert!(process.is_alive());
+
+        child.stdin.take().unwrap().write_all(b"\n").unwrap();
+        assert_eq!(exited.recv_timeout(Duration::from_secs(5)), Ok(Some(3)));
+
+        // Not reaped yet, the zombie still reports its exit code. The failed read
+        // finds out on its own that the target exited.
+        assert_eq!(
+            process.read_value::<u8>(0x1000),
+            Err(Errors::ProcessExited {
+                pid: child.id(),
+                code: Some(3)
+            })
+        );
+        assert!(!process.is_alive());
+        assert!(process.read_slice::<u8>(0x1000, 1).is_err());
+        child.wait().unwrap();
+        assert!(!process.is_alive());
+    }
+
+    #[test]
+    fn flags_successes_after_exit_as_stale() {
+        use std::process::Command;
+
+        let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+        let handle = open_process(child.id(), OpenOptions::new().read(true)).unwrap();
+        assert!(!is_stale(&handle));
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        // Reaped, so the PID is free for reuse by an unrelated process
+        assert!(is_stale(&handle));
+        close_pidfd(handle);
+    }
+
+    #[test]
+    fn enumerates_own_process() {
+        let pid = std::process::id();
+        let entry = processes().find(|entry| entry.pid() == pid).unwrap();
+        assert_eq!(entry.parent_pid(), std::os::unix::process::parent_id());
+        assert_eq!(entry.exe_path(), std::env::current_exe().ok());
+        assert_eq!(
+            entry.architecture().map(Architecture::pointer_width),
+            Some(size_of::<usize>())
+        );
+
+        let instances = find_processes(&entry.name().to_ascii_uppercase());
+        assert!(instances.contains(&entry));
+    }
+
+    #[test]
+    fn reads_elf_architecture() {
+        let mut header = *b"\x7FELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0\x3E\0";
+        assert_eq!(elf_architecture(&header), Some(Architecture::X86_64));
+        header[18] = 0x03;
+        assert_eq!(elf_architecture(&header), Some(Architecture::X86));
+        assert_eq!(elf_architecture(b"MZ\x90\0"), None);
+    }
+
+    #[test]
+    fn reads_and_writes_own_memory() {
+        let process = current_process();
+        let target = Box::new(0usize);
+        let pointer = Box::new(&raw const *target as usize);
+
+        write(&process, &raw const *target as usize, &0x1337usize);
+        assert_eq!(black_box(*target), 0x1337);
+
+        let mut value = 0usize;
+        read(&process, &raw const *pointer as usize, &[0], &mut value);
+        assert_eq!(value, 0x1337);
+    }
+
+    #[test]
+    fn reads_scattered_with_iovecs() {
+        let values = black_box([0x11u32, 0x22, 0x33]);
+        let process = current_process();
+
+        let mut first = [0u8; 4];
+        let mut unmapped = [0u8; 4];
+        let mut last = [0u8; 8];
+        let mut requests: [(usize, &mut [u8]); 3] = [
+            (&raw const values[0] as usize, &mut first),
+            (0, &mut unmapped),
+            (&raw const values[1] as usize, &mut last),
+        ];
+
+        let results = process.read_scattered(&mut requests);
+        assert_eq!(results[0].as_ref().ok(), Some(&4));
+        assert_eq!(
+            results[1].as_ref().unwrap_err().raw_os_error(),
+            Some(libc::EFAULT)
+        );
+        assert_eq!(results[2].as_ref().ok(), Some(&8));
+        assert_eq!(u32::from_ne_bytes(first), 0x11);
+        assert_eq!(last, (0x33u64 << 32 | 0x22).to_le_bytes());
+    }
+
+    #[test]
+    fn reports_os_error_code() {
+        let mut value = 0usize;
+        let err = current_process().try_read(0, &[], &mut value).unwrap_err();
+        assert_eq!(
+            err,
+            Errors::ReadFailed {
+                pid: Some(std::process::id()),
+                address: 0,
+                requested: size_of::<usize>(),
+                transferred: 0,
+                os_error: Some(OsError(libc::EFAULT)),
+            }
+        );
+
+        let boxed: Box<dyn Error + Send + Sync> = err.into();
+        let source = boxed.source().unwrap();
+        assert_eq!(source.downcast_ref(), Some(&OsError(libc::EFAULT)));
+        assert_eq!(
+            source.to_string(),
+            io::Error::from_raw_os_error(libc::EFAULT).to_string()
+        );
+    }
+
+    #[test]
+    fn opens_with_minimal_rights() {
+        let value = Box::new(0x1337usize);
+        let address = &raw const *value as usize;
+        let process = OpenOptions::new()
+            .read(true)
+            .open(std::process::id())
+            .unwrap();
+
+        assert_eq!(process.read_value::<usize>(address), Ok(0x1337));
+        assert_eq!(
+            process.try_write(address, &0usize),
+            Err(Errors::MissingAccess(Access::Write))
+        );
+        assert!(process.modules().is_empty());
+        assert_eq!(black_box(*value), 0x1337);
+    }
+
+    #[test]
+    fn reports_missing_process() {
+        let err = OpenOptions::new().read(true).open(u32::MAX).unwrap_err();
+        assert_eq!(err, Errors::ProcessNotFound);
+    }
+
+    #[test]
+    fn finds_signature_in_own_memory() {
+        let process = current_process();
+        let address = black_box(&SIGNATURE).as_ptr() as usize;
+        let found = process
+            .find_signature(
+                address - 0x100,
+                0x200,
+                &Pattern::parse("13 37 67 61 6D 65 68 61 63 6B C0 DE").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(found, address);
+    }
+}
+
+mod mock {
+    use crate::{
+        Errors, MemoryAccess, Pattern,
+        mock::MockProcess,
+        read,
+        types::Protection,
+        utils::{
+            find_signature, find_signature_all, find_signature_iter, find_unique_signature,
+            process_modules,
+        },
+        write,
+    };
+
+    fn game() -> MockProcess {
+        MockProcess::new()
+            .region(0x1000, [0u8; 0x100], Protection::RX)
+            .region(0x1100, [0u8; 0x100], Protection::RW)
+            .region(0x4000, [0u8; 0x40], Protection::RW)
+            .module("Game.exe", 0x1000, 0x200)
+    }
+
+    #[test]
+    fn follows_pointer_chain() {
+        let process = game();
+        process.poke(0x1100, &0x4000usize.to_ne_bytes());
+        process.poke(0x4018, &0x4030usize.to_ne_bytes());
+        process.poke(0x4038, &0xDEADusize.to_ne_bytes());
+
+        let mut value = 0usize;
+        read(&process, 0x1100, &[0x18, 0x8], &mut value);
+        assert_eq!(value, 0xDEAD);
+    }
+
+    #[test]
+    fn scans_signature_across_regions() {
+        let process = game();
+        process.poke(0x10F0, b"\x48\x8D\x05\x7A\xB9");
+        process.poke(0x1150, b"\x48\x8D\x05\x11\x22\x48\x89");
+
+        let pattern = Pattern::from_code(b"\x48\x8D\x05\x00\x00\x48", "xxx??x").unwrap();
+        assert_eq!(
+            find_signature(&process, 0x1000, 0x200, &pattern),
+            Ok(0x1150)
+        );
+        assert_eq!(
+            find_signature(&process, 0x1000, 0x100, &pattern),
+            Err(Errors::SignatureNotFound)
+        );
+    }
+
+    #[test]
+    fn scans_signature_across_boundaries() {
+        let process = game();
+        process.poke(0x10FD, b"\xE8\x11\x22\x33\x44\xC3");
+        process.poke(0x1120, b"\xE8\x55\x66\x77\x88\xC3");
+
+        let pattern = Pattern::parse("E8 ? ? ? ? C3").unwrap();
+        assert_eq!(
+            find_signature_all(&process, 0x1000, 0x200, &pattern),
+            [0x10FD, 0x1120]
+        );
+        for chunk_size in [1, 3, 0x10, 0x21, 0x1000] {
+            let matches = find_signature_iter(&process, 0x1000, 0x200, &pattern)
+                .chunk_size(chunk_size)
+                .collect::<Vec<_>>();
+            assert_eq!(matches, [0x10FD, 0x1120], "chunk size {chunk_size:#X}");
+        }
+        assert_eq!(
+            find_signature_all(&process, 0x1100, 0x100, &pattern),
+            [0x1120]
+        );
+    }
 
     #[test]
-    fn found_process() {
-        assert!(find_process("svchost.exe").is_ok())
+    fn does_not_join_separated_regions() {
+        let process = game().region(0x4040, [0u8; 0x40], Protection::NONE);
+        process.poke(0x403E, b"\x90\x90");
+        process.poke(0x1100, b"\xCC");
+        process.poke(0x10FF, b"\xCC");
+
+        let pattern = Pattern::parse("90 90 ? ?").unwrap();
+        assert!(find_signature_all(&process, 0x1000, 0x4100, &pattern).is_empty());
+        assert_eq!(
+            find_signature_all(&process, 0x1000, 0x4100, &Pattern::parse("CC CC").unwrap()),
+            [0x10FF]
+        );
     }
 
     #[test]
-    fn not_found_process() {
-        use crate::Errors;
-        assert_eq!(find_process("").err().unwrap(), Errors::ProcessNotFound)
+    fn finds_every_signature_match() {
+        let process = game();
+        process.poke(0x1010, b"\x90\x90\x90");
+        process.poke(0x1180, b"\x90\x90");
+
+        let pattern = Pattern::parse("90 90").unwrap();
+        assert_eq!(
+            find_signature_all(&process, 0x1000, 0x200, &pattern),
+            [0x1010, 0x1011, 0x1180]
+        );
+        assert_eq!(
+            find_unique_signature(&process, 0x1000, 0x200, &pattern),
+            Err(Errors::AmbiguousSignature { matches: 3 })
+        );
+        assert_eq!(
+            find_unique_signature(&process, 0x1100, 0x100, &pattern),
+            Ok(0x1180)
+        );
+        assert_eq!(
+            find_unique_signature(&process, 0x1100, 0x80, &pattern),
+            Err(Errors::SignatureNotFound)
+        );
+    }
+
+    #[test]
+    fn respects_protections() {
+        let process = game();
+        write(&process, 0x1000, &0xFFu8);
+        write(&process, 0x4000, &0xFFu8);
+        assert_eq!(process.peek(0x1000, 1), Some(vec![0]));
+        assert_eq!(process.peek(0x4000, 1), Some(vec![0xFF]));
+    }
+
+    #[test]
+    fn reports_partial_reads() {
+        let process = game();
+        let mut buffer = [0u8; 0x20];
+        assert_eq!(process.read_bytes(0x11F0, &mut buffer).unwrap(), 0x10);
+        assert!(process.read_bytes(0x3000, &mut buffer).is_err());
+    }
+
+    #[test]
+    fn lists_modules() {
+        let modules = process_modules(&game());
+        assert_eq!(modules["game.exe"].module_addr, 0x1000);
+    }
+}
+
+mod fallible {
+    use crate::{Errors, OsError, mock::MockProcess, try_read, try_write, types::Protection};
+
+    #[test]
+    fn reports_broken_chain() {
+        let process = MockProcess::new().region(0x1000, 0x2000usize.to_ne_bytes(), Protection::RO);
+
+        let mut value = 0usize;
+        assert_eq!(
+            try_read(&process, 0x1000, &[0x10], &mut value),
+            Err(Errors::ReadFailed {
+                pid: None,
+                address: 0x2010,
+                requested: size_of::<usize>(),
+                transferred: 0,
+                os_error: None,
+            })
+        );
+        assert_eq!(value, 0);
+    }
+
+    #[test]
+    fn reports_partial_read() {
+        let process = MockProcess::new().region(0x1000, [0u8; 4], Protection::RO);
+
+        let mut value = 0usize;
+        let err = try_read(&process, 0x1000, &[], &mut value).unwrap_err();
+        assert!(matches!(err, Errors::ReadFailed { transferred: 4, .. }));
+    }
+
+    #[test]
+    fn reports_failed_write() {
+        let process = MockProcess::new().region(0x1000, [0u8; 4], Protection::RO);
+
+        let err = try_write(&process, 0x1000, &1u32).unwrap_err();
+        assert!(matches!(
+            err,
+            Errors::WriteFailed {
+                address: 0x1000,
+                transferred: 0,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Failed to write 4 bytes at 0x1000 (0 transferred)"
+        );
+    }
+
+    #[test]
+    fn formats_os_errors_alike() {
+        let os_error = Some(OsError(14));
+        let messages = [
+            Errors::ReadFailed {
+                pid: Some(7),
+                address: 0x1000,
+                requested: 8,
+                transferred: 0,
+                os_error,
+            },
+            Errors::BrokenPointerPath {
+                pid: Some(7),
+                level: 1,
+                address: 0x1000,
+                os_error,
+            },
+            Errors::AccessDenied {
+                pid: 7,
+                access: None,
+                os_error,
+            },
+        ]
+        .map(|err| err.to_string());
+        assert_eq!(
+            messages,
+            [
+                "Failed to read 8 bytes at 0x1000 in process 7 (0 transferred), OS error 14",
+                "Pointer path broken at level 1: 0x1000 in process 7 is unreadable, OS error 14",
+                "Access to process 7 denied, OS error 14",
+            ]
+        );
+    }
+}
+
+mod chains {
+    use super::zeroed;
+    use crate::{
+        mock::MockProcess, read, read_chain, read_value, resolve_chain, types::Protection,
+    };
+
+    fn process() -> MockProcess {
+        let process = zeroed(&[(0x1000, 0x10)]).region(0x2000, [0xAAu8; 0x20], Protection::RW);
+        process.poke(0x1000, &0x2000usize.to_ne_bytes());
+        process.poke(0x2010, &0x1234_5678u32.to_ne_bytes());
+        process
+    }
+
+    #[test]
+    fn reads_exact_size() {
+        let process = process();
+        assert_eq!(read_value::<_, u32>(&process, 0x2010), Ok(0x1234_5678));
+        assert_eq!(read_value::<_, u8>(&process, 0x2014), Ok(0xAA));
+    }
+
+    #[test]
+    fn resolves_without_dereferencing_last_hop() {
+        let process = process();
+        assert_eq!(resolve_chain(&process, 0x1000, &[]), Ok(0x1000));
+        assert_eq!(resolve_chain(&process, 0x1000, &[0x10]), Ok(0x2010));
+        assert_eq!(
+            read_chain::<_, u32>(&process, 0x1000, &[0x10]),
+            Ok(0x1234_5678)
+        );
+    }
+
+    #[test]
+    fn read_does_not_overrun_small_buffers() {
+        let process = process();
+        let mut values = [0u32, 0xFFFF_FFFF];
+        read(&process, 0x1000, &[0x10], &mut values[0]);
+        assert_eq!(values, [0x1234_5678, 0xFFFF_FFFF]);
+    }
+}
+
+mod pointer_width {
+    use crate::{
+        PointerPath,
+        expression::{Environment, Expression},
+        mock::MockProcess,
+        resolve_chain,
+        types::{Architecture, Protection},
+        utils::resolve_relative,
+    };
+
+    /// A 32-bit target whose pointers are followed by garbage in the high half.
+    fn process() -> MockProcess {
+        let process = MockProcess::new()
+            .architecture(Architecture::X86)
+            .region(0x1000, [0xEEu8; 0x10], Protection::RW)
+            .region(0x2000, [0xEEu8; 0x20], Protection::RW)
+            .module("game.exe", 0x1000, 0x10);
+        process.poke(0x1000, &0x2000u32.to_le_bytes());
+        process.poke(0x2010, &0x3000u32.to_le_bytes());
+        process
+    }
+
+    #[test]
+    fn follows_four_byte_pointers() {
+        let process = process();
+        assert_eq!(resolve_chain(&process, 0x1000, &[0x10, 8]), Ok(0x3008));
+
+        let path = PointerPath::parse("game.exe -> 10 -> 8").unwrap();
+        assert_eq!(path.resolve(&process), Ok(0x3008));
+
+        let expr = Expression::parse("[[game.exe]+0x10]+8").unwrap();
+        assert_eq!(expr.evaluate(&process, &Environment::new()), Ok(0x3008));
+    }
+
+    #[test]
+    fn wraps_relative_targets_at_four_gib() {
+        let process = MockProcess::new().architecture(Architecture::X86).region(
+            0x1000,
+            [0u8; 0x10],
+            Protection::RW,
+        );
+        // call -0x2000 at 0x1000 lands below zero and wraps in a 32-bit process
+        process.poke(0x1000, &[0xE8]);
+        process.poke(0x1001, &(-0x2000i32).to_le_bytes());
+        assert_eq!(resolve_relative(&process, 0x1000, 1, 5), Ok(0xFFFF_F005));
+
+        let process = MockProcess::new()
+            .architecture(Architecture::X86_64)
+            .region(0x1000, [0u8; 0x10], Protection::RW);
+        process.poke(0x1003, &0x10i32.to_le_bytes());
+        assert_eq!(resolve_relative(&process, 0x1000, 3, 7), Ok(0x1017));
+    }
+}
+
+mod pointer_path {
+    use super::zeroed;
+    use crate::{Errors, PointerPath};
+
+    #[test]
+    fn parses_cheat_engine_notation() {
+        let expected = PointerPath::new(Some("hitman3.exe"), 0x22B_AF18, &[0x18, -0x10]);
+        assert_eq!(
+            PointerPath::parse(r#""hitman3.exe"+022BAF18 -> 0x18 -> -10"#),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            PointerPath::parse(r#"[["hitman3.exe"+022BAF18]+18]-0x10"#),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            expected.to_string(),
+            r#""hitman3.exe"+22BAF18 -> 18 -> -10"#
+        );
+        assert_eq!(
+            PointerPath::parse("client.dll+10-4"),
+            Ok(PointerPath::new(Some("client.dll"), 0xC, &[]))
+        );
+        assert_eq!(
+            PointerPath::parse("7FF6A000 -> 8"),
+            Ok(PointerPath::new(None, 0x7FF6_A000, &[8]))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_paths() {
+        assert_eq!(
+            PointerPath::parse("game.exe -> 0xZZ"),
+            Err(Errors::InvalidPointerPath("0xZZ".to_string()))
+        );
+        assert_eq!(
+            PointerPath::parse("[[game.exe]+8"),
+            Err(Errors::InvalidPointerPath("[[game.exe]+8".to_string()))
+        );
+        assert_eq!(
+            PointerPath::parse("a.dll+b.dll"),
+            Err(Errors::InvalidPointerPath("b.dll".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_separators_inside_quoted_modules() {
+        assert_eq!(
+            PointerPath::parse(r#"["a]b"+10]+4"#),
+            Ok(PointerPath::new(Some("a]b"), 0x10, &[4]))
+        );
+        assert_eq!(
+            PointerPath::parse(r#"[["a[b"+10]+4]+8"#),
+            Ok(PointerPath::new(Some("a[b"), 0x10, &[4, 8]))
+        );
+        assert_eq!(
+            PointerPath::parse(r#""a->b"+10 -> 4"#),
+            Ok(PointerPath::new(Some("a->b"), 0x10, &[4]))
+        );
+        assert!(PointerPath::parse(r#"["a]b+10]+4"#).is_err());
+    }
+
+    #[test]
+    fn rejects_repeated_signs() {
+        for path in [
+            "game.exe -> -+5",
+            "game.exe -> ++10",
+            "game.exe -> 0x+10",
+            "game.exe+0x-10",
+            "++10",
+            "game.exe+-10",
+        ] {
+            assert!(
+                matches!(PointerPath::parse(path), Err(Errors::InvalidPointerPath(_))),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            PointerPath::parse("game.exe -> +10 -> -0x8"),
+            Ok(PointerPath::new(Some("game.exe"), 0, &[0x10, -8]))
+        );
+    }
+
+    #[test]
+    fn resolves_and_reports_broken_level() {
+        let process = zeroed(&[(0x1000, 0x20), (0x2000, 0x20)]).module("game.exe", 0x1000, 0x20);
+        process.poke(0x1010, &0x2000usize.to_ne_bytes());
+        process.poke(0x2008, &0x3000usize.to_ne_bytes());
+        process.poke(0x2018, &7u32.to_ne_bytes());
+
+        let path = PointerPath::parse("game.exe+10 -> 18").unwrap();
+        assert_eq!(path.resolve(&process), Ok(0x2018));
+        assert_eq!(path.read::<u32, _>(&process), Ok(7));
+
+        let path = PointerPath::parse("[[[game.exe+10]+8]]+4").unwrap();
+        assert_eq!(path.offsets(), &[8, 0, 4]);
+        assert_eq!(
+            path.resolve(&process),
+            Err(Errors::BrokenPointerPath {
+                pid: None,
+                level: 2,
+                address: 0x3000,
+                os_error: None
+            })
+        );
+        assert_eq!(
+            PointerPath::parse("other.dll+10").unwrap().base(&process),
+            Err(Errors::ModuleNotFound("other.dll".to_string()))
+        );
+    }
+}
+
+mod expressions {
+    use super::zeroed;
+    use crate::{
+        Errors,
+        expression::{Environment, Expression, MAX_NESTING},
+        mock::MockProcess,
+    };
+
+    fn process() -> MockProcess {
+        let process = zeroed(&[(0x1000, 0x20), (0x2000, 0x20)])
+            .module("client.dll", 0x1000, 0x20)
+            .module("my-game.exe", 0x2000, 0x20);
+        process.poke(0x1010, &0x2000usize.to_ne_bytes());
+        process.poke(0x2018, &0x1122_3344_5566_7788u64.to_ne_bytes());
+        process
+    }
+
+    fn eval(expression: &str, environment: &Environment) -> usize {
+        Expression::parse(expression)
+            .unwrap()
+            .evaluate(&process(), environment)
+            .unwrap()
+    }
+
+    #[test]
+    fn evaluates_arithmetic() {
+        let env = Environment::new().variable("idx", 3);
+        assert_eq!(eval("1 + 2 * 3 - 0x10", &env), 7usize.wrapping_sub(16));
+        assert_eq!(eval("(1 + 2) * -idx", &env), 9usize.wrapping_neg());
+        assert_eq!(eval(r#""my-game.exe" + 24"#, &env), 0x2018);
+    }
+
+    #[test]
+    fn evaluates_nested_dereferences() {
+        let env = Environment::new().variable("idx", 2);
+        assert_eq!(
+            eval("[[client.dll+0x10]+0x18]+4*idx", &env),
+            0x1122_3344_5566_7790
+        );
+        assert_eq!(eval("dword[[client.dll+0x10]+0x18]", &env), 0x5566_7788);
+        assert_eq!(
+            eval("[[client.dll+0x10]+0x18]", &env.clone().pointer_width(2)),
+            0x7788
+        );
+    }
+
+    #[test]
+    fn reads_names_starting_with_digits() {
+        let process = process().module("2dengine.dll", 0x1000, 0x20);
+        let expression = Expression::parse("[2dengine.dll + 0x10] + 3rd").unwrap();
+        let env = Environment::new().variable("3rd", 1);
+        assert_eq!(expression.evaluate(&process, &env), Ok(0x2001));
+    }
+
+    #[test]
+    fn evaluates_long_flat_chains() {
+        let env = Environment::new();
+        let sum = vec!["1"; 100_000].join("+");
+        assert_eq!(eval(&format!("{sum} - 0x10"), &env), 100_000 - 16);
+
+        let product = vec!["2"; 100_000].join(" * ");
+        assert_eq!(eval(&product, &env), 0);
+        assert_eq!(
+            eval(&format!("[{sum} - 100000 + client.dll + 0x10]"), &env),
+            0x2000
+        );
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = |depth: usize, open: &str, close: &str| {
+            format!("{}client.dll{}", open.repeat(depth), close.repeat(depth))
+        };
+        assert!(Expression::parse(&nested(MAX_NESTING, "[", "]")).is_ok());
+        assert!(Expression::parse(&nested(MAX_NESTING, "(", ")")).is_ok());
+
+        for (open, close) in [("[", "]"), ("(", ")"), ("dword[", "]"), ("-", "")] {
+            let expression = nested(100_000, open, close);
+            assert!(
+                matches!(
+                    Expression::parse(&expression),
+                    Err(Errors::InvalidExpression(_))
+                ),
+                "{open}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(
+            Expression::parse("[client.dll + 0x1G]"),
+            Err(Errors::InvalidExpression("0x1G".to_string()))
+        );
+        assert_eq!(
+            Expression::parse("[client.dll + 1"),
+            Err(Errors::InvalidExpression("".to_string()))
+        );
+        assert_eq!(
+            Expression::parse("1 2"),
+            Err(Errors::InvalidExpression("2".to_string()))
+        );
+        assert_eq!(
+            Expression::parse("client.dll + 1F"),
+            Err(Errors::InvalidExpression("1F".to_string()))
+        );
+
+        let expression = Expression::parse("server.dll + 8").unwrap();
+        assert_eq!(
+            expression.evaluate(&process(), &Environment::new()),
+            Err(Errors::UnknownSymbol("server.dll".to_string()))
+        );
+        let expression = Expression::parse("[0x3000]").unwrap();
+        assert!(matches!(
+            expression.evaluate(&process(), &Environment::new()),
+            Err(Errors::ReadFailed {
+                address: 0x3000,
+                ..
+            })
+        ));
+    }
+}
+
+mod strings {
+    use super::zeroed;
+    use crate::{
+        Errors,
+        mock::MockProcess,
+        strings::{
+            Decoding, Encoding, read_cstring, read_string, read_terminated_string,
+            read_utf16_string, write_cstring, write_string, write_utf16_string,
+        },
+        types::Protection,
+    };
+
+    fn utf16(value: &str) -> Vec<u8> {
+        value.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn reads_cstring_up_to_unmapped_page() {
+        let process = MockProcess::new().region(0x1000, [0xAAu8; 0x1000], Protection::RW);
+        process.poke(0x1FF9, b"Player\0");
+
+        assert_eq!(read_cstring(&process, 0x1FF9, 64), Ok("Player".to_string()));
+
+        process.poke(0x1FFF, b"!");
+        assert!(matches!(
+            read_cstring(&process, 0x1FF9, 64),
+            Err(Errors::ReadFailed {
+                address: 0x2000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn reports_unterminated_and_invalid_strings() {
+        let process = zeroed(&[(0x1000, 0x20)]);
+        process.poke(0x1000, b"abcdefgh");
+        process.poke(0x1010, b"\xFFok\0");
+
+        assert_eq!(
+            read_cstring(&process, 0x1000, 8),
+            Err(Errors::UnterminatedString {
+                address: 0x1000,
+                max_len: 8
+            })
+        );
+        assert_eq!(
+            read_cstring(&process, 0x1000, 9),
+            Ok("abcdefgh".to_string())
+        );
+        assert_eq!(
+            read_cstring(&process, 0x1010, 16),
+            Err(Errors::InvalidString {
+                address: 0x1010,
+                encoding: Encoding::Utf8
+            })
+        );
+        assert_eq!(
+            read_terminated_string(&process, 0x1010, 16, Encoding::Utf8, Decoding::Lossy),
+            Ok("\u{FFFD}ok".to_string())
+        );
+        assert_eq!(
+            read_terminated_string(&process, 0x1010, 16, Encoding::Latin1, Decoding::Strict),
+            Ok("\u{FF}ok".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_utf16_across_chunks() {
+        let process = zeroed(&[(0x1000, 0x2000)]);
+        process.poke(0x1FFB, &utf16("Hélène"));
+
+        assert_eq!(
+            read_utf16_string(&process, 0x1FFB, 32),
+            Ok("Hélène".to_string())
+        );
+        assert_eq!(
+            read_utf16_string(&process, 0x1FFB, 6),
+            Err(Errors::UnterminatedString {
+                address: 0x1FFB,
+                max_len: 6
+            })
+        );
+
+        process.poke(0x1100, &[0x00, 0xD8, b'a', 0, 0, 0]);
+        assert!(matches!(
+            read_utf16_string(&process, 0x1100, 8),
+            Err(Errors::InvalidString { .. })
+        ));
+        assert_eq!(
+            read_terminated_string(&process, 0x1100, 8, Encoding::Utf16Le, Decoding::Lossy),
+            Ok("\u{FFFD}a".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_fixed_length_strings() {
+        let process = zeroed(&[(0x1000, 0x20)]);
+        process.poke(0x1000, b"name\0\0\0\0full");
+
+        let read = |addr, len| read_string(&process, addr, len, Encoding::Utf8, Decoding::Strict);
+        assert_eq!(read(0x1000, 8), Ok("name".to_string()));
+        assert_eq!(read(0x1008, 4), Ok("full".to_string()));
+        assert!(matches!(read(0x1008, 0x20), Err(Errors::ReadFailed { .. })));
+    }
+
+    #[test]
+    fn writes_bounded_strings() {
+        let process = MockProcess::new().region(0x1000, [0xAAu8; 0x20], Protection::RW);
+
+        write_cstring(&process, 0x1000, "abc", 4).unwrap();
+        assert_eq!(process.peek(0x1000, 5), Some(b"abc\0\xAA".to_vec()));
+        assert_eq!(
+            write_cstring(&process, 0x1000, "abcd", 4),
+            Err(Errors::StringTooLong { len: 5, max_len: 4 })
+        );
+        assert_eq!(process.peek(0x1000, 5), Some(b"abc\0\xAA".to_vec()));
+
+        write_utf16_string(&process, 0x1010, "ok", 3).unwrap();
+        assert_eq!(read_utf16_string(&process, 0x1010, 3), Ok("ok".to_string()));
+        assert_eq!(
+            write_string(&process, 0x1010, "€", Encoding::Latin1, 8),
+            Err(Errors::InvalidString {
+                address: 0x1010,
+                encoding: Encoding::Latin1
+            })
+        );
+    }
+}
+
+mod slices {
+    use crate::{Errors, mock::MockProcess, read_into, read_slice, types::Protection};
+
+    /// Three pages of `u32` indices with the middle one unreadable.
+    fn entities() -> MockProcess {
+        let page =
+            |first: u32| -> Vec<u8> { (first..first + 0x400).flat_map(u32::to_le_bytes).collect() };
+        MockProcess::new()
+            .region(0x1000, page(0), Protection::RW)
+            .region(0x2000, page(0x400), Protection::NONE)
+            .region(0x3000, page(0x800), Protection::RW)
+    }
+
+    #[test]
+    fn reads_slice_in_one_go() {
+        let process = entities();
+        assert_eq!(
+            read_slice::<_, u32>(&process, 0x1010, 4),
+            Ok(vec![4, 5, 6, 7])
+        );
+        assert_eq!(read_slice::<_, u64>(&process, 0x1000, 0), Ok(vec![]));
+    }
+
+    #[test]
+    fn rejects_oversized_counts_before_allocating() {
+        let process = entities();
+        let too_large = |address, count, element_size| Errors::SliceTooLarge {
+            address,
+            count,
+            element_size,
+        };
+
+        assert_eq!(
+            read_slice::<_, u32>(&process, 0x1000, usize::MAX),
+            Err(too_large(0x1000, usize::MAX, 4))
+        );
+        assert_eq!(
+            read_slice::<_, u8>(&process, usize::MAX, 2),
+            Err(too_large(usize::MAX, 2, 1))
+        );
+        // 4 MiB of garbage count, but only 12 KiB are mapped
+        assert_eq!(
+            read_slice::<_, u32>(&process, 0x1000, 0x10_0000),
+            Err(too_large(0x1000, 0x10_0000, 4))
+        );
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn reports_unreadable_elements() {
+        let process = entities();
+
+        let mut values = [u32::MAX; 0x801];
+        assert_eq!(
+            read_into(&process, 0x1FFC, &mut values),
+            Err(Errors::UnreadableElements {
+                address: 0x1FFC,
+                unreadable: vec![1..0x401],
+            })
+        );
+        assert_eq!(values[0], 0x3FF);
+        assert!(values[1..0x401].iter().all(|&value| value == 0));
+        assert_eq!(values[0x401], 0x800);
+        assert_eq!(values[0x800], 0xBFF);
+
+        // An element straddling the boundary of an unreadable page is unreadable too
+        let mut pairs = [[0u32; 2]; 2];
+        assert_eq!(
+            read_into(&process, 0x2FFC, &mut pairs),
+            Err(Errors::UnreadableElements {
+                address: 0x2FFC,
+                unreadable: vec![0..1],
+            })
+        );
+        assert_eq!(pairs, [[0, 0], [0x801, 0x802]]);
+    }
+
+    #[test]
+    fn reports_read_failure_without_readable_elements() {
+        let process = entities();
+        assert!(matches!(
+            read_slice::<_, u32>(&process, 0x2000, 8),
+            Err(Errors::ReadFailed {
+                address: 0x2000,
+                ..
+            })
+        ));
+    }
+}
+
+mod pods {
+    use super::zeroed;
+    use crate::{Pod, RemotePtr, read_chain, read_slice, read_value, write};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Pod)]
+    #[repr(C)]
+    struct Vector3 {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Pod)]
+    #[repr(C)]
+    struct Transform {
+        position: Vector3,
+        flags: [u8; 4],
+        owner: RemotePtr<u64>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Pod)]
+    #[repr(transparent)]
+    struct EntityId(u32);
+
+    #[test]
+    fn reads_arrays() {
+        let process = zeroed(&[(0x1000, 0x40)]);
+        process.poke(0x1000, &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(
+            read_value::<_, [u8; 8]>(&process, 0x1000),
+            Ok([1, 2, 3, 4, 5, 6, 7, 8])
+        );
+        assert_eq!(
+            read_value::<_, [[u16; 2]; 2]>(&process, 0x1000),
+            Ok([[0x0201, 0x0403], [0x0605, 0x0807]])
+        );
+
+        write(&process, 0x1010, &[1.5f32, -2.0]);
+        assert_eq!(
+            read_slice::<_, [f32; 2]>(&process, 0x1010, 2),
+            Ok(vec![[1.5, -2.0], [0.0, 0.0]])
+        );
+    }
+
+    #[test]
+    fn reads_user_structs() {
+        let process = zeroed(&[(0x1000, 0x40)]);
+        let transform = Transform {
+            position: Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+            flags: [1, 0, 0, 1],
+            owner: RemotePtr::new(0x1030),
+        };
+        write(&process, 0x1000, &transform);
+        write(&process, 0x1030, &EntityId(47));
+        process.poke(0x1038, &0x1000usize.to_ne_bytes());
+
+        assert_eq!(read_value(&process, 0x1000), Ok(transform));
+        assert_eq!(read_value(&process, 0x1030), Ok(EntityId(47)));
+        assert_eq!(read_chain(&process, 0x1038, &[0x4]), Ok(2.0f32));
+        assert_eq!(
+            read_slice::<_, Vector3>(&process, 0x1000, 1),
+            Ok(vec![transform.position])
+        );
+    }
+}
+
+mod batches {
+    use super::{Counting, zeroed};
+    use crate::{Errors, batch::BatchRead};
+
+    fn entities() -> Counting {
+        let process = zeroed(&[(0x1000, 0x1000), (0x3000, 0x1000)]);
+        for (index, addr) in [0x1000, 0x1100, 0x1180, 0x3000].into_iter().enumerate() {
+            process.poke(addr, &(index as u32).to_le_bytes());
+        }
+        Counting::new(process)
+    }
+
+    #[test]
+    fn coalesces_close_ranges() {
+        let memory = entities();
+
+        let mut batch = BatchRead::new();
+        let slots = [0x1180, 0x1000, 0x1100, 0x3000].map(|addr| batch.push::<u32>(addr));
+        let results = batch.read(&memory);
+
+        assert_eq!(slots.map(|slot| results.get(slot)), [2, 0, 1, 3].map(Ok));
+        assert_eq!(memory.reads.get(), 2);
+    }
+
+    #[test]
+    fn reports_each_request() {
+        let memory = entities();
+
+        let mut batch = BatchRead::new();
+        let first = batch.push::<u32>(0x1FFC);
+        let straddling = batch.push::<u64>(0x1FFC);
+        let unmapped = batch.push_bytes(0x2100, 4);
+        let last = batch.push::<u32>(0x3000);
+        let results = batch.read(&memory);
+
+        assert_eq!(results.get(first), Ok(0));
+        assert_eq!(
+            results.get(straddling),
+            Err(Errors::ReadFailed {
+                pid: None,
+                address: 0x1FFC,
+                requested: 8,
+                transferred: 4,
+                os_error: None,
+            })
+        );
+        assert!(matches!(
+            results.bytes(unmapped),
+            Err(Errors::ReadFailed {
+                address: 0x2100,
+                transferred: 0,
+                ..
+            })
+        ));
+        assert_eq!(results.get(last), Ok(3));
+    }
+}
+
+mod remote_structs {
+    use super::{Counting, zeroed};
+    use crate::{
+        Errors, RemoteStruct,
+        mock::MockProcess,
+        remote::{MAX_POINTER_DEPTH, read_struct},
+        types::{Architecture, Protection},
+    };
+
+    #[derive(Debug, PartialEq, RemoteStruct)]
+    struct Weapon {
+        #[offset(0x4)]
+        ammo: u16,
+    }
+
+    #[derive(Debug, PartialEq, RemoteStruct)]
+    struct Player {
+        #[offset(0x10)]
+        position: [f32; 3],
+        #[offset(0x8)]
+        health: u32,
+        #[offset(0x20)]
+        #[pointer]
+        weapon: Weapon,
+        #[offset(0x28)]
+        #[pointer]
+        target: Option<Box<Player>>,
+    }
+
+    fn world() -> MockProcess {
+        let process = zeroed(&[(0x1000, 0x30), (0x2000, 0x6)]);
+        process.poke(0x1008, &75u32.to_ne_bytes());
+        process.poke(0x1014, &1.5f32.to_ne_bytes());
+        process.poke(0x1020, &0x2000usize.to_le_bytes());
+        process.poke(0x2004, &12u16.to_ne_bytes());
+        process
+    }
+
+    #[test]
+    fn reads_struct_in_one_read() {
+        let memory = Counting::new(world());
+        assert_eq!(Player::remote_size(8), 0x30);
+        assert_eq!(
+            read_struct(&memory, 0x1000),
+            Ok(Player {
+                position: [0.0, 1.5, 0.0],
+                health: 75,
+                weapon: Weapon { ammo: 12 },
+                target: None,
+            })
+        );
+        // The player and the weapon behind its pointer
+        assert_eq!(memory.reads.get(), 2);
+    }
+
+    #[test]
+    fn follows_nested_pointers() {
+        let process = world().region(0x3000, [0u8; 0x30], Protection::RW);
+        process.poke(0x1028, &0x3000usize.to_le_bytes());
+        process.poke(0x3008, &20u32.to_ne_bytes());
+        process.poke(0x3020, &0x2000usize.to_le_bytes());
+
+        let player: Player = read_struct(&process, 0x1000).unwrap();
+        let target = player.target.unwrap();
+        assert_eq!(target.health, 20);
+        assert_eq!(target.weapon, Weapon { ammo: 12 });
+        assert_eq!(target.target, None);
+    }
+
+    #[test]
+    fn stops_at_pointer_cycles() {
+        let process = world().region(0x3000, [0u8; 0x30], Protection::RW);
+        process.poke(0x1028, &0x3000usize.to_le_bytes());
+        process.poke(0x3020, &0x2000usize.to_le_bytes());
+        process.poke(0x3028, &0x1000usize.to_le_bytes());
+
+        // The players target each other; the weapon of the last one is too deep
+        assert_eq!(
+            read_struct::<_, Player>(&process, 0x1000),
+            Err(Errors::PointerDepthExceeded {
+                address: 0x2000,
+                max_depth: MAX_POINTER_DEPTH,
+            })
+        );
+    }
+
+    #[test]
+    fn follows_pointers_of_the_target_width() {
+        #[derive(Debug, PartialEq, RemoteStruct)]
+        struct Pointers {
+            #[offset(0x0)]
+            #[pointer]
+            first: Weapon,
+            #[offset(0x4)]
+            #[pointer]
+            second: Option<Weapon>,
+        }
+
+        let process = world().architecture(Architecture::X86);
+        process.poke(0x1000, &0x2000u32.to_le_bytes());
+        process.poke(0x1004, &0u32.to_le_bytes());
+
+        assert_eq!(Pointers::remote_size(4), 8);
+        assert_eq!(
+            read_struct(&process, 0x1000),
+            Ok(Pointers {
+                first: Weapon { ammo: 12 },
+                second: None,
+            })
+        );
+        assert!(matches!(
+            read_struct::<_, Weapon>(&process, 0x5000),
+            Err(Errors::ReadFailed { .. })
+        ));
+    }
+}
+
+mod remote_ptrs {
+    use super::zeroed;
+    use crate::{
+        Errors, RemotePtr, RemoteStruct, mock::MockProcess, remote::read_struct,
+        types::Architecture,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(C)]
+    struct Vec3 {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    unsafe impl crate::Pod for Vec3 {}
+
+    #[derive(Debug, RemoteStruct)]
+    struct Player {
+        #[offset(0x10)]
+        #[pointer]
+        position: RemotePtr<Vec3>,
+    }
+
+    #[test]
+    fn navigates_arrays_and_pointers() {
+        let process = zeroed(&[(0x1000, 0x20), (0x2000, 0x20)]).architecture(Architecture::X86);
+        // Player* players[2] of a 32-bit target
+        process.poke(0x1004, &0x2000u32.to_le_bytes());
+        process.poke(0x2010, &0x2014u32.to_le_bytes());
+
+        let players = RemotePtr::<RemotePtr<Player>>::new(0x1000);
+        assert!(players.deref(&process).unwrap().is_null());
+        assert_eq!(
+            players.index(1).address(),
+            0x1000 + size_of::<RemotePtr<Player>>()
+        );
+        assert_eq!(players.index_ptr(&process, 1).address(), 0x1004);
+        assert_eq!(
+            players.index_ptr(&process, 1).deref(&process),
+            Ok(RemotePtr::new(0x2000))
+        );
+        assert_eq!(
+            players.offset_ptr(&process, 2).offset_ptr(&process, -1),
+            players.index_ptr(&process, 1)
+        );
+
+        let second = RemotePtr::<RemotePtr<Player>>::new(0x1004).deref(&process);
+        assert_eq!(second, Ok(RemotePtr::new(0x2000)));
+        let player = second.unwrap().read_struct(&process).unwrap();
+        assert_eq!(player.position, RemotePtr::new(0x2014));
+
+        player
+            .position
+            .write(
+                &process,
+                &Vec3 {
+                    x: 1.0,
+                    y: 2.0,
+                    z: 3.0,
+                },
+            )
+            .unwrap();
+        let y = player.position.cast::<f32>().offset(1);
+        assert_eq!(y.address(), 0x2018);
+        assert_eq!(y.read(&process), Ok(2.0));
+        assert_eq!(y.offset(-1).byte_offset(8).read(&process), Ok(3.0));
+        assert_eq!(
+            player.position.cast::<f32>().read_slice(&process, 3),
+            Ok(vec![1.0, 2.0, 3.0])
+        );
+    }
+
+    #[test]
+    fn reports_unreadable_targets() {
+        let process = MockProcess::new();
+        let pointer = RemotePtr::<u32>::null();
+        assert!(pointer.is_null());
+        assert_eq!(pointer.to_string(), "0x0");
+        assert!(matches!(
+            pointer.read(&process),
+            Err(Errors::ReadFailed { address: 0, .. })
+        ));
+        assert!(matches!(
+            read_struct::<_, Player>(&process, 0x10),
+            Err(Errors::ReadFailed { address: 0x10, .. })
+        ));
+    }
+}
+
+mod patterns {
+    use crate::{Errors, Pattern, pattern};
+
+    #[test]
+    fn parses_ida_style() {
+        let pattern = Pattern::parse(" 48 8d 05 ? ?? 4? ?F  ").unwrap();
+        assert_eq!(pattern.bytes(), &[0x48, 0x8D, 0x05, 0, 0, 0x40, 0x0F]);
+        assert_eq!(pattern.mask(), &[0xFF, 0xFF, 0xFF, 0, 0, 0xF0, 0x0F]);
+        assert_eq!(pattern.to_string(), "48 8D 05 ? ? 4? ?F");
+        assert_eq!(pattern.to_string().parse(), Ok(pattern.clone()));
+
+        assert!(pattern.matches(b"\x48\x8D\x05\x12\x34\x4A\xBF\xFF"));
+        assert!(!pattern.matches(b"\x48\x8D\x05\x12\x34\x5A\xBF"));
+        assert!(!pattern.matches(b"\x48\x8D\x05"));
+
+        assert_eq!(Pattern::parse("488D05????"), Pattern::parse("48 8D 05 ? ?"));
+    }
+
+    #[test]
+    fn parses_at_compile_time() {
+        const SIGNATURE: &str = " 48 8d 05 ? ?? 4? ?F 488D ";
+        static PATTERN: Pattern = pattern!(SIGNATURE);
+
+        assert_eq!(Ok(PATTERN.clone()), Pattern::parse(SIGNATURE));
+        assert_eq!(pattern!("C3"), Pattern::parse("C3").unwrap());
+        assert_eq!(
+            Pattern::from_static(&[0x48, 0x40], &[0xFF, 0xF0]),
+            pattern!("48 4?")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert_eq!(
+            Pattern::parse("48 8G 05"),
+            Err(Errors::InvalidPattern("8G".to_string()))
+        );
+        assert_eq!(
+            Pattern::parse("48 8D5"),
+            Err(Errors::InvalidPattern("8D5".to_string()))
+        );
+        assert_eq!(Pattern::parse("  "), Err(Errors::EmptyPattern));
+    }
+
+    #[test]
+    fn builds_from_code_style() {
+        let pattern = Pattern::from_code(b"\x48\x8D\x05\x7A\xB9", "xxx??").unwrap();
+        assert_eq!(Ok(pattern), Pattern::parse("48 8D 05 ? ?"));
+
+        assert_eq!(
+            Pattern::from_code(b"\x48\x8D\x05", "xx??"),
+            Err(Errors::PatternLengthMismatch { bytes: 3, mask: 4 })
+        );
+        assert_eq!(
+            Pattern::from_code(b"\x48\x8D", "x."),
+            Err(Errors::InvalidPattern(".".to_string()))
+        );
+        assert_eq!(Pattern::from_code(b"", ""), Err(Errors::EmptyPattern));
+    }
+
+    #[test]
+    fn find_agrees_with_every_position() {
+        let data = (0u32..0x2000)
+            .map(|i| (i.wrapping_mul(0x9E37_79B9) >> 27) as u8)
+            .collect::<Vec<_>>();
+
+        for signature in [
+            "00",
+            "1F 00 ? 0?",
+            "? ? 03",
+            "?1 ?? 0?",
+            "00 1F 03 1E 1F 1F 09 ? 1D 00",
+        ] {
+            let pattern = Pattern::parse(signature).unwrap();
+            for start in [0, 1, 0x7F, 0x1FF0] {
+                let haystack = &data[start..];
+                let expected = haystack
+                    .windows(pattern.len())
+                    .position(|window| pattern.matches(window));
+                assert_eq!(
+                    pattern.find(haystack),
+                    expected,
+                    "{signature} from {start:#X}"
+                );
+            }
+        }
+        assert_eq!(pattern!("C3").find(&[]), None);
+        assert_eq!(pattern!("48 8B ?").find(b"\x48\x8B"), None);
+        assert_eq!(
+            pattern!("48 8B ? ? ? ? ? ? ? 48 8D")
+                .find(b"\x48\x48\x8B\x01\x02\x03\x04\x05\x06\x07\x48\x8D"),
+            Some(1)
+        );
     }
 }
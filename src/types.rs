@@ -1,9 +1,6 @@
 use std::ffi::CStr;
 
-use windows::Win32::Foundation::HANDLE;
-
 use crate::errors::Errors;
-use std::collections::HashMap;
 
 /// Represents metadata for a specific module (DLL or EXE) within a process.
 ///
@@ -16,30 +13,78 @@ pub struct ModuleData {
     pub module_addr: usize,
     pub module_size: usize,
 }
-/// A container for process-specific information and its associated system handle.
-///
-/// This structure centralizes the identification ([`u32`]), access ([`HANDLE`]),
-/// and memory map ([`HashMap`]) of a target process.
-///
-/// # Type Parameters
-///
-/// * `K` - The type of the key used in the `module_list`. A [`String`]
-///   representing the module name or a [`usize`] for its base address.
+
+/// Access rights of a committed memory region.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Protection {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Protection {
+    /// No access, e.g. a reserved or guard page.
+    pub const NONE: Self = Self::new(false, false, false);
+    /// Read-only, e.g. constants and string tables.
+    pub const RO: Self = Self::new(true, false, false);
+    /// Readable and writable, e.g. the heap, stacks and globals.
+    pub const RW: Self = Self::new(true, true, false);
+    /// Readable and executable, e.g. code.
+    pub const RX: Self = Self::new(true, false, true);
+
+    const fn new(read: bool, write: bool, execute: bool) -> Self {
+        Self {
+            read,
+            write,
+            execute,
+        }
+    }
+}
+
+/// Describes a contiguous range of committed pages in the target process.
 ///
-/// # Safety and Resource Management
+/// Produced by [`MemoryAccess::regions`](crate::memory::MemoryAccess::regions) from
+/// `VirtualQueryEx` on Windows or `/proc/<pid>/maps` on Linux.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub base: usize,
+    pub size: usize,
+    pub protection: Protection,
+}
+
+impl MemoryRegion {
+    /// Returns the first address past the end of the region.
+    #[must_use]
+    pub fn end(&self) -> usize {
+        self.base.wrapping_add(self.size)
+    }
+}
+
+/// Instruction set a target process runs as, which decides its pointer width.
 ///
-/// - **Handle Ownership**: The `handle` field is a raw Win32 [`HANDLE`]. This struct
-///   does **not** automatically close the handle upon being dropped. The caller
-///   must ensure [`close_handle`](crate::close_handle) is called to prevent resource leaks.
-/// - **Memory Layout**: Marked with `#[repr(C)]` for a fixed field order, aiding
-///   integration with external analysis tools.
+/// A 32-bit game running under WOW64 on 64-bit Windows reports [`Architecture::X86`].
 #[repr(C)]
-#[derive(Debug, Clone, Default)]
-pub struct ProcessData<K> {
-    pub handle: HANDLE,
-    pub id: u32,
-    pub module_list: HashMap<K, ModuleData>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
 }
+
+impl Architecture {
+    /// Returns the size of a pointer in bytes.
+    #[must_use]
+    pub fn pointer_width(self) -> usize {
+        match self {
+            Architecture::X86 | Architecture::Arm => 4,
+            Architecture::X86_64 | Architecture::Aarch64 => 8,
+        }
+    }
+}
+
 /// A trait for converting raw identifiers or buffers into normalized, lowercase strings.
 ///
 /// This trait is primarily used to handle the conversion of null-terminated byte
@@ -57,7 +102,7 @@ pub trait TransformName {
     ///
     /// Returns [`Errors::NoNulByte`] if no null terminator is found in the slice,
     /// or [`Errors::InvalidUtf8`] if the sequence is not valid UTF-8.
-    fn to_string_lowercase(&self) -> Result<String, Errors<'_>>;
+    fn to_string_lowercase(&self) -> Result<String, Errors>;
 }
 
 /// Implementation of [`TransformName`] for byte slices.
@@ -77,7 +122,7 @@ impl TransformName for [u8] {
     ///
     /// Returns [`Errors::NoNulByte`] if no null terminator is found in the slice,
     /// or [`Errors::InvalidUtf8`] if the sequence is not valid UTF-8.
-    fn to_string_lowercase(&self) -> Result<String, Errors<'_>> {
+    fn to_string_lowercase(&self) -> Result<String, Errors> {
         Ok(CStr::from_bytes_until_nul(self)?
             .to_str()?
             .to_ascii_lowercase())
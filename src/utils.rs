@@ -1,33 +1,22 @@
-use windows::Win32::{
-    Foundation::{HANDLE, HMODULE},
-    System::{
-        Diagnostics::Debug::ReadProcessMemory,
-        Memory::{MEM_FREE, MEMORY_BASIC_INFORMATION, VirtualQueryEx},
-        ProcessStatus::{EnumProcessModules, GetModuleBaseNameA, GetModuleInformation, MODULEINFO},
-    },
-};
+use std::{collections::HashMap, ops::Range};
 
 use crate::{
-    errors::Errors,
-    types::{ModuleData, ProcessData},
+    errors::Errors, memory::MemoryAccess, pattern::Pattern, read_value, types::ModuleData,
 };
-use std::ptr::{addr_of_mut, null_mut};
-
-use crate::types::TransformName;
 
 /// Searches for a byte pattern (signature) within a specific memory range of a process.
 ///
-/// This function iterates through memory regions of a target process using [`VirtualQueryEx`],
-/// reads non-free memory segments, and attempts to find a match for a provided byte
-/// signature and mask.
+/// This function iterates through the memory regions of a target process reported by
+/// [`MemoryAccess::regions`], reads the readable segments overlapping the range, and
+/// attempts to find a match for a [`Pattern`].
 ///
 /// # Arguments
 ///
-/// * `handle` - A valid [`HANDLE`] to the target process with `PROCESS_VM_READ` and `PROCESS_QUERY_INFORMATION` access.
+/// * `memory` - Any [`MemoryAccess`] backend of the target process with read and query access.
 /// * `base` - The starting memory address for the scan.
 /// * `size` - The total size of the memory range to scan.
-/// * `sign` - A byte slice (`&[u8]`) representing the pattern to search for.
-/// * `mask` - A string slice where `'?'` represents a wildcard (skip) and `'x'` represents an exact match for the corresponding byte in `sign`.
+/// * `pattern` - The [`Pattern`] to search for, e.g. parsed from
+///   `"48 8D 05 ? ? ? ? 48 89 41 18"`.
 ///
 /// # Returns
 ///
@@ -36,57 +25,230 @@ use crate::types::TransformName;
 ///
 /// # Technical Details
 ///
-/// 1. **Region Traversal**: Uses [`VirtualQueryEx`] to identify allocated memory pages, skipping `MEM_FREE` regions to improve performance and avoid errors.
-/// 2. **Scanning**: For each valid region, it copies the entire memory block into a local buffer before performing the pattern match.
-/// 3. **Comparison**: Uses `data_compare` (internally) to evaluate the signature against the buffer using the provided mask.
+/// 1. **Region Traversal**: Uses [`MemoryAccess::regions`] to identify committed memory pages, skipping unreadable regions to improve performance and avoid errors. Adjacent readable regions are joined, so a signature may span several of them.
+/// 2. **Scanning**: Reads the range in chunks of [`SCAN_CHUNK`] bytes into one reused buffer; consecutive chunks overlap by the pattern length. See [`find_signature_iter`].
+/// 3. **Comparison**: Uses [`Pattern::find`], which only compares the pattern where the buffer holds its rarest exact byte.
+pub fn find_signature<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    base: usize,
+    size: usize,
+    pattern: &Pattern,
+) -> Result<usize, Errors> {
+    find_signature_iter(memory, base, size, pattern)
+        .next()
+        .ok_or(Errors::SignatureNotFound)
+}
+
+/// Returns the address of every match of `pattern` in the range, in ascending order.
 ///
-/// # Performance Warning
+/// Overlapping matches are reported separately. Use it to check how many places a
+/// signature hits after a game update; see [`find_signature`] for the scan itself.
+pub fn find_signature_all<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    base: usize,
+    size: usize,
+    pattern: &Pattern,
+) -> Vec<usize> {
+    find_signature_iter(memory, base, size, pattern).collect()
+}
+
+/// Searches the range for `pattern` and insists that it matches exactly once.
 ///
-/// This function allocates a `Vec<u8>` the size of each memory region (often 4KB or more) per iteration. For very large search ranges, this may cause significant temporary memory pressure
+/// # Errors
 ///
-pub fn find_signature<'a>(
-    handle: HANDLE,
+/// * [`Errors::SignatureNotFound`] if the pattern was not found.
+/// * [`Errors::AmbiguousSignature`] with the number of matches if it was found more
+///   than once.
+pub fn find_unique_signature<M: MemoryAccess + ?Sized>(
+    memory: &M,
     base: usize,
     size: usize,
-    sign: &'a [u8],
-    mask: &'a str,
-) -> Result<usize, Errors<'a>> {
-    let mut mbi = MEMORY_BASIC_INFORMATION::default();
-    let mut offset = 0;
-
-    while offset < size {
-        unsafe {
-            let address = (base + offset) as *const _;
-            VirtualQueryEx(
-                handle,
-                Some(address),
-                addr_of_mut!(mbi),
-                size_of::<MEMORY_BASIC_INFORMATION>(),
-            );
-
-            if mbi.State != MEM_FREE {
-                let region_size = mbi.RegionSize;
-                let mut buffer = vec![0u8; region_size];
-
-                let _ = ReadProcessMemory(
-                    handle,
-                    address,
-                    buffer.as_mut_ptr().cast(),
-                    region_size,
-                    Some(null_mut()),
-                );
-
-                if let Some(offset) = buffer
-                    .windows(sign.len())
-                    .position(|buffer| data_compare(buffer, sign, mask))
-                {
-                    return Ok((mbi.BaseAddress as usize).wrapping_add(offset));
-                }
+    pattern: &Pattern,
+) -> Result<usize, Errors> {
+    let mut matches = find_signature_iter(memory, base, size, pattern);
+    let first = matches.next().ok_or(Errors::SignatureNotFound)?;
+    match matches.count() {
+        0 => Ok(first),
+        more => Err(Errors::AmbiguousSignature { matches: more + 1 }),
+    }
+}
+
+/// Returns a lazy iterator over the matches of `pattern` in the range.
+///
+/// Memory is read in chunks of [`SCAN_CHUNK`] bytes into a single buffer that is
+/// reused for the whole scan, so stopping early skips the rest of the range and
+/// huge regions need no huge allocations. Adjacent readable regions are scanned as
+/// one block and consecutive chunks overlap by the pattern length, so matches
+/// that straddle a region or chunk boundary are found as well.
+///
+/// # Example
+///
+/// ```
+/// use gamehack_librs::{mock::MockProcess, pattern, types::Protection, utils::find_signature_iter};
+///
+/// let process = MockProcess::new().region(0x1000, [0u8; 0x100], Protection::RX);
+/// process.poke(0x1010, b"\x48\x8D\x05");
+/// process.poke(0x1080, b"\x48\x8D\x0D");
+///
+/// let lea = pattern!("48 8D 0?");
+/// let mut matches = find_signature_iter(&process, 0x1000, 0x100, &lea);
+/// assert_eq!(matches.next(), Some(0x1010));
+/// assert_eq!(matches.next(), Some(0x1080));
+/// assert_eq!(matches.next(), None);
+/// ```
+pub fn find_signature_iter<'a, M: MemoryAccess + ?Sized>(
+    memory: &'a M,
+    base: usize,
+    size: usize,
+    pattern: &'a Pattern,
+) -> SignatureMatches<'a, M> {
+    let end = base.saturating_add(size);
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    for region in memory
+        .regions()
+        .into_iter()
+        .filter(|region| region.protection.read && region.base < end && region.end() > base)
+    {
+        let span = region.base.max(base)..region.end().min(end);
+        match spans.last_mut() {
+            Some(last) if last.end == span.start => last.end = span.end,
+            _ => spans.push(span),
+        }
+    }
+
+    SignatureMatches {
+        memory,
+        pattern,
+        spans: spans.into_iter(),
+        cursor: 0,
+        span_end: 0,
+        chunk_size: SCAN_CHUNK,
+        buffer: Vec::new(),
+        start: 0,
+        offset: 0,
+    }
+}
+
+/// The number of bytes [`find_signature_iter`] reads at a time by default.
+pub const SCAN_CHUNK: usize = 0x10000;
+
+/// The matches of a [`Pattern`], returned by [`find_signature_iter`].
+pub struct SignatureMatches<'a, M: MemoryAccess + ?Sized> {
+    memory: &'a M,
+    pattern: &'a Pattern,
+    /// The blocks of adjacent readable regions that have not been reached yet.
+    spans: std::vec::IntoIter<Range<usize>>,
+    /// The next address to read and the end of its block.
+    cursor: usize,
+    span_end: usize,
+    chunk_size: usize,
+    /// The bytes read last, starting at address `start`.
+    buffer: Vec<u8>,
+    start: usize,
+    /// The position in `buffer` to continue searching from.
+    offset: usize,
+}
+
+impl<M: MemoryAccess + ?Sized> SignatureMatches<'_, M> {
+    /// Sets the number of bytes read at a time, [`SCAN_CHUNK`] by default.
+    ///
+    /// Smaller chunks find the first match of a large range with less reading,
+    /// larger ones need fewer calls into the target.
+    #[must_use]
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Reads the next chunk, keeping the tail of the previous one if it directly
+    /// precedes it. Returns `None` once the range is exhausted.
+    fn refill(&mut self) -> Option<()> {
+        if self.cursor >= self.span_end {
+            let span = self.spans.next()?;
+            self.cursor = span.start;
+            self.span_end = span.end;
+            self.buffer.clear();
+        }
+
+        // Matches starting in the kept tail did not fit into the previous chunk, so
+        // none of them has been reported yet.
+        let keep = if self.start.wrapping_add(self.buffer.len()) == self.cursor {
+            self.buffer.len().min(self.pattern.len() - 1)
+        } else {
+            0
+        };
+        self.buffer.drain(..self.buffer.len() - keep);
+
+        let len = self.chunk_size.min(self.span_end - self.cursor);
+        self.buffer.resize(keep + len, 0);
+        let read = self
+            .memory
+            .read_bytes(self.cursor, &mut self.buffer[keep..])
+            .unwrap_or(0);
+        self.buffer.truncate(keep + read);
+
+        self.start = self.cursor - keep;
+        self.cursor += len;
+        self.offset = 0;
+        Some(())
+    }
+}
+
+impl<M: MemoryAccess + ?Sized> Iterator for SignatureMatches<'_, M> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if let Some(found) = self
+                .buffer
+                .get(self.offset..)
+                .and_then(|rest| self.pattern.find(rest))
+            {
+                let position = self.offset + found;
+                self.offset = position + 1;
+                return Some(self.start.wrapping_add(position));
             }
+            self.refill()?;
         }
-        offset += mbi.RegionSize;
     }
-    Err(Errors::SignatureNotFound)
+}
+
+/// Resolves the target of a relative operand in an instruction, typically one found
+/// with [`find_signature`].
+///
+/// x86 `call`/`jmp rel32` and x64 RIP-relative operands such as
+/// `lea rax, [rip + disp32]` store a signed 32-bit displacement from the end of the
+/// instruction. The sum is computed in the target's
+/// [`pointer_width`](MemoryAccess::pointer_width), so on 32-bit targets it wraps
+/// around at 4 GiB like the CPU does.
+///
+/// # Arguments
+///
+/// * `memory` - Any [`MemoryAccess`] backend of the target process with read access.
+/// * `instruction` - The address of the instruction.
+/// * `displacement_offset` - The offset of the displacement within the instruction,
+///   e.g. 3 for `48 8D 05 <disp32>`.
+/// * `instruction_len` - The length of the whole instruction, e.g. 7 for the `lea` above.
+///
+/// # Errors
+///
+/// Returns [`Errors::ReadFailed`] if the displacement could not be read.
+pub fn resolve_relative<M: MemoryAccess + ?Sized>(
+    memory: &M,
+    instruction: usize,
+    displacement_offset: usize,
+    instruction_len: usize,
+) -> Result<usize, Errors> {
+    let displacement: i32 = read_value(memory, instruction.wrapping_add(displacement_offset))?;
+    let target = instruction
+        .wrapping_add(instruction_len)
+        .wrapping_add_signed(displacement as isize);
+
+    Ok(if memory.pointer_width() == 4 {
+        target as u32 as usize
+    } else {
+        target
+    })
 }
 
 /// Compares a block of memory against a byte pattern using a mask.
@@ -106,6 +268,9 @@ pub fn find_signature<'a>(
 ///
 /// Returns `true` if the `data` matches the `sign` under the given `mask`.
 /// Returns `false` if the inputs are inconsistent or the pattern doesn't match.
+///
+/// The mask is interpreted again on every call; scans should build a [`Pattern`]
+/// once with [`Pattern::from_code`] and use [`Pattern::find`] instead.
 #[must_use]
 pub fn data_compare(data: &[u8], sign: &[u8], mask: &str) -> bool {
     if data.len() < mask.len() || sign.len() < mask.len() {
@@ -116,73 +281,31 @@ pub fn data_compare(data: &[u8], sign: &[u8], mask: &str) -> bool {
         .all(|(idx, c)| c != 'x' || data[idx] == sign[idx])
 }
 
-/// Populates the provided [`ProcessData`] with a list of all loaded modules.
+/// Collects all loaded modules of the target process.
 ///
-/// This function enumerates all modules (DLLs and the main executable) within
-/// the context of the process identified by the handle in `process_data`. It
-/// gathers the name, base address, and image size for each module.
+/// This function enumerates all modules (shared libraries and the main executable)
+/// within the target process via [`MemoryAccess::modules`] and gathers the name,
+/// base address, and image size for each module.
 ///
 /// # Arguments
 ///
-/// * `process_data` - A mutable reference to a [`ProcessData`] struct. The
-///   `handle` field must be a valid process handle with `PROCESS_QUERY_INFORMATION`
-///   and `PROCESS_VM_READ` access.
+/// * `memory` - Any [`MemoryAccess`] backend of the target process. On Windows the
+///   handle needs `PROCESS_QUERY_INFORMATION` and `PROCESS_VM_READ` access.
 ///
 /// # Behavior
 ///
-/// 1. **Enumeration**: Calls `EnumProcessModules` to retrieve up to 1024 module handles.
-/// 2. **Metadata Collection**: For each module, it queries the base name via
-///    `GetModuleBaseNameA` and memory information via `GetModuleInformation`.
-/// 3. **State Mutation**: Updates the `module_list` hash map within the `process_data`
-///    struct. Module names are normalized to lowercase.
-///
-/// # Safety
-///
-/// This function internally uses `unsafe` blocks to interface with the Windows API.
-/// It assumes the `process_data.handle` is valid and has not been closed.
-///
-pub fn process_modules(process_data: &mut ProcessData<String>) {
-    let mut mod_list = [HMODULE::default(); 1024];
-    let mut cb_needed = 0;
-    let handle = process_data.handle;
-
-    unsafe {
-        let _ = EnumProcessModules(
-            handle,
-            mod_list.as_mut_ptr().cast(),
-            size_of_val(&mod_list) as u32,
-            addr_of_mut!(cb_needed),
-        );
-    }
-
-    for &mod_handle in mod_list
-        .iter()
-        .take(cb_needed as usize / size_of::<HMODULE>())
-    {
-        let mut name = [0u8; 256];
-        let mut mi = MODULEINFO::default();
-
-        unsafe {
-            let _ = GetModuleBaseNameA(handle, Some(mod_handle), &mut name);
-            let _ = GetModuleInformation(
-                handle,
-                mod_handle,
-                addr_of_mut!(mi),
-                size_of::<MODULEINFO>() as u32,
-            );
-        }
-
-        let name = name
-            .to_string_lowercase()
-            .unwrap_or("<Module Name>".to_string());
-
-        process_data.module_list.insert(
-            name.clone(),
-            ModuleData {
-                module_name: name,
-                module_addr: mi.lpBaseOfDll as usize,
-                module_size: mi.SizeOfImage as usize,
-            },
-        );
-    }
+/// 1. **Enumeration**: `EnumProcessModulesEx` (`LIST_MODULES_32BIT` for WOW64
+///    targets, so they list their own 32-bit modules) with a buffer grown until
+///    every module handle fits on Windows, the file-backed mappings of `/proc/<pid>/maps` on Linux.
+/// 2. **Metadata Collection**: For each module, the base name, base address and
+///    image size are collected.
+/// 3. **Result**: Returns a hash map keyed by the module name, normalized to lowercase.
+///
+#[must_use]
+pub fn process_modules<M: MemoryAccess + ?Sized>(memory: &M) -> HashMap<String, ModuleData> {
+    memory
+        .modules()
+        .into_iter()
+        .map(|module| (module.module_name.clone(), module))
+        .collect()
 }
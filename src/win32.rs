@@ -0,0 +1,554 @@
+use std::{
+    ffi::OsString,
+    io,
+    os::windows::ffi::OsStringExt,
+    path::PathBuf,
+    ptr::{addr_of_mut, null},
+};
+
+use windows::{
+    Win32::{
+        Foundation::{
+            CloseHandle, ERROR_INVALID_PARAMETER, HANDLE, HMODULE, STILL_ACTIVE, WIN32_ERROR,
+        },
+        System::{
+            Diagnostics::{
+                Debug::{ReadProcessMemory, WriteProcessMemory},
+                ToolHelp::{
+                    CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+                    TH32CS_SNAPPROCESS,
+                },
+            },
+            Memory::{
+                MEM_COMMIT, MEMORY_BASIC_INFORMATION, PAGE_EXECUTE, PAGE_EXECUTE_READ,
+                PAGE_EXECUTE_READWRITE, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD, PAGE_NOACCESS,
+                PAGE_PROTECTION_FLAGS, PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY,
+                VirtualQueryEx,
+            },
+            ProcessStatus::{
+                EnumProcessModulesEx, GetModuleBaseNameA, GetModuleInformation, LIST_MODULES_32BIT,
+                LIST_MODULES_DEFAULT, MODULEINFO,
+            },
+            SystemInformation::{
+                IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64,
+                IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386, IMAGE_FILE_MACHINE_UNKNOWN,
+            },
+            Threading::{
+                GetExitCodeProcess, GetProcessId, INFINITE, IsWow64Process2, OpenProcess,
+                PROCESS_ACCESS_RIGHTS, PROCESS_ALL_ACCESS, PROCESS_NAME_WIN32,
+                PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SYNCHRONIZE,
+                PROCESS_VM_OPERATION, PROCESS_VM_READ, PROCESS_VM_WRITE,
+                QueryFullProcessImageNameW, WaitForSingleObject,
+            },
+        },
+    },
+    core::{Error, PWSTR},
+};
+
+use crate::{
+    enumeration::ProcessEntry,
+    errors::{Errors, OsError},
+    memory::MemoryAccess,
+    options::{Access, OpenOptions},
+    types::{Architecture, MemoryRegion, ModuleData, Protection, TransformName},
+};
+
+/// The raw process handle used by the Windows backend.
+pub(crate) type RawHandle = HANDLE;
+
+/// Opens a local process and returns a handle with full access rights.
+///
+/// This function wraps the Win32 [`OpenProcess`] call. It is used to obtain a
+/// handle that allows for extensive operations, including reading/writing memory
+/// and querying process information.
+///
+/// # Arguments
+///
+/// * `pid` - The unique process identifier (PID) of the target process.
+///
+/// # Returns
+///
+/// * `Ok(HANDLE)` - A valid, open handle to the process if successful.
+/// * `Err(Error)` - An error indicating failure, such as if the process does not exist
+///   or the current user lacks sufficient privileges (e.g., `ERROR_ACCESS_DENIED`).
+///
+/// # Security Warning
+///
+/// This function requests **`PROCESS_ALL_ACCESS`**. In modern Windows environments (2026),
+/// this may require the calling process to have `SeDebugPrivilege` enabled or to
+/// be running with Administrative privileges. Excessive permissions may
+/// also trigger Attack Surface Reduction (ASR) rules or EDR alerts.
+///
+/// # Safety
+///
+/// This function uses an `unsafe` block to call a foreign API. It is considered
+/// a safe wrapper because:
+/// 1. It validates the return value of `OpenProcess`.
+/// 2. It converts the null-handle failure state into a standard Rust [`Result`].
+///
+/// **Note:** The caller is responsible for eventually closing the returned handle
+/// using [`close_handle`] to prevent resource leaks. Prefer [`Process::open`](crate::Process::open) or
+/// [`OpenOptions::open`], which own the handle, close it on drop and can request
+/// fewer rights.
+pub fn get_process_handle(pid: u32) -> Result<HANDLE, Error> {
+    unsafe { OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_ALL_ACCESS, false, pid) }
+}
+
+/// Closes an open object handle.
+///
+/// This is a safe wrapper around the Win32 [`CloseHandle`] function. It ensures
+/// that system resources associated with the handle are released.
+///
+/// # Arguments
+///
+/// * `handle` - A valid [`HANDLE`] to an open object (e.g., process, thread, or file).
+///
+/// # Side Effects
+///
+/// Closing a handle invalidates the handle value, making it unusable for further calls.
+/// Note that for some objects, like threads or processes, closing the handle does not
+/// terminate the object; it only removes your access to it.
+///
+/// # Safety
+///
+/// While this function is marked as `pub`, it wraps an `unsafe` block. It assumes
+/// that the provided `handle` is either a valid open handle or `NULL`.
+/// Passing a pseudo-handle or an already closed handle may lead to undefined
+/// behavior in some Windows environments, although `CloseHandle` usually
+/// just returns an error.
+pub fn close_handle(handle: HANDLE) {
+    unsafe {
+        // We ignore the return value (BOOL) as there is often little
+        // recovery logic possible if a handle fails to close.
+        let _ = CloseHandle(handle);
+    }
+}
+
+/// Translates [`OpenOptions`] into the minimal set of Win32 process access rights.
+///
+/// `PROCESS_QUERY_LIMITED_INFORMATION` is always requested, since
+/// `GetExitCodeProcess` needs it to detect that the target exited.
+fn access_rights(options: &OpenOptions) -> PROCESS_ACCESS_RIGHTS {
+    let mut rights = PROCESS_QUERY_LIMITED_INFORMATION;
+    if options.allows(Access::Read) {
+        rights |= PROCESS_VM_READ;
+    }
+    if options.allows(Access::Write) {
+        rights |= PROCESS_VM_WRITE | PROCESS_VM_OPERATION;
+    }
+    if options.allows(Access::Query) {
+        // EnumProcessModulesEx reads the module list from the memory of the target
+        rights |= PROCESS_QUERY_INFORMATION | PROCESS_VM_READ;
+    }
+    if options.allows(Access::Operation) {
+        rights |= PROCESS_VM_OPERATION;
+    }
+    if options.allows(Access::Synchronize) {
+        rights |= PROCESS_SYNCHRONIZE;
+    }
+    rights
+}
+
+/// Opens the process with the given `pid` for [`OpenOptions::open`].
+///
+/// `OpenProcess` fails as a whole, so the refused right is found by asking for each
+/// requested right on its own.
+pub(crate) fn open_process(pid: u32, options: &OpenOptions) -> Result<RawHandle, Errors> {
+    unsafe { OpenProcess(access_rights(options), false, pid) }.map_err(|err| {
+        let code = WIN32_ERROR::from_error(&err);
+        if code == Some(ERROR_INVALID_PARAMETER) {
+            Errors::ProcessNotFound
+        } else {
+            Errors::AccessDenied {
+                pid,
+                access: refused_access(pid, options),
+                os_error: code.map(|code| OsError(code.0.cast_signed())),
+            }
+        }
+    })
+}
+
+/// Returns the first right requested by `options` that `OpenProcess` refuses on its
+/// own, or `None` if each of them is granted alone.
+fn refused_access(pid: u32, options: &OpenOptions) -> Option<Access> {
+    Access::ALL
+        .into_iter()
+        .filter(|&access| options.allows(access))
+        .find(|&access| {
+            let rights = access_rights(&OpenOptions::only(access));
+            match unsafe { OpenProcess(rights, false, pid) } {
+                Ok(handle) => {
+                    close_handle(handle);
+                    false
+                }
+                Err(_) => true,
+            }
+        })
+}
+
+/// Converts the error returned by a Win32 call into an [`io::Error`] with its
+/// Win32 error code, without consulting the thread's last error again.
+fn io_error(err: &Error) -> io::Error {
+    match WIN32_ERROR::from_error(err) {
+        Some(code) => io::Error::from_raw_os_error(code.0.cast_signed()),
+        None => io::Error::other(err.message()),
+    }
+}
+
+/// Checks whether the process behind `handle` has exited.
+///
+/// Returns `Some` with the exit code once `GetExitCodeProcess` no longer reports
+/// `STILL_ACTIVE`, `None` while it is running or if the handle lacks
+/// `PROCESS_QUERY_LIMITED_INFORMATION`. A process that exits with code 259
+/// (`STILL_ACTIVE`) is indistinguishable from a running one.
+pub(crate) fn exit_code(handle: &RawHandle) -> Option<Option<i32>> {
+    let mut code = 0u32;
+    unsafe { GetExitCodeProcess(*handle, addr_of_mut!(code)) }.ok()?;
+    (code.cast_signed() != STILL_ACTIVE.0).then_some(Some(code.cast_signed()))
+}
+
+/// Returns a blocking function that waits for process `pid` to exit.
+///
+/// A separate `SYNCHRONIZE` handle is opened for the waiting thread. The PID cannot
+/// be reused while `_handle` is open, so it refers to the same process.
+pub(crate) fn exit_waiter(
+    _handle: &RawHandle,
+    pid: u32,
+) -> Result<impl FnOnce() -> Option<i32> + Send + 'static, Errors> {
+    let options = *OpenOptions::new().synchronize(true);
+    let waitable = open_process(pid, &options)?;
+    // HANDLE is not Send, the raw value is
+    let waitable = waitable.0 as usize;
+
+    Ok(move || {
+        let waitable = HANDLE(waitable as *mut _);
+        unsafe { WaitForSingleObject(waitable, INFINITE) };
+        let code = exit_code(&waitable).flatten();
+        close_handle(waitable);
+        code
+    })
+}
+
+/// Checks, after an operation succeeded, whether it may have reached a process other
+/// than the target. Never the case, as the PID cannot be reused while `_handle` is open.
+pub(crate) fn is_stale(_handle: &RawHandle) -> bool {
+    false
+}
+
+/// Walks a ToolHelp process snapshot, closing it when exhausted or dropped.
+pub(crate) struct ProcessIter {
+    snapshot: Option<HANDLE>,
+    entry: PROCESSENTRY32W,
+    started: bool,
+}
+
+/// Takes a process snapshot for [`processes`](crate::processes).
+pub(crate) fn processes() -> ProcessIter {
+    ProcessIter {
+        snapshot: unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }.ok(),
+        entry: PROCESSENTRY32W {
+            dwSize: size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        },
+        started: false,
+    }
+}
+
+impl Iterator for ProcessIter {
+    type Item = ProcessEntry;
+
+    fn next(&mut self) -> Option<ProcessEntry> {
+        let snapshot = self.snapshot?;
+        let result = unsafe {
+            if self.started {
+                Process32NextW(snapshot, addr_of_mut!(self.entry))
+            } else {
+                Process32FirstW(snapshot, addr_of_mut!(self.entry))
+            }
+        };
+        self.started = true;
+
+        if result.is_err() {
+            close_handle(snapshot);
+            self.snapshot = None;
+            return None;
+        }
+
+        let name = &self.entry.szExeFile;
+        let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+        Some(ProcessEntry::new(
+            self.entry.th32ProcessID,
+            self.entry.th32ParentProcessID,
+            String::from_utf16_lossy(&name[..len]),
+        ))
+    }
+}
+
+impl Drop for ProcessIter {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            close_handle(snapshot);
+        }
+    }
+}
+
+/// Runs `query` on a short-lived `PROCESS_QUERY_LIMITED_INFORMATION` handle to `pid`.
+fn with_limited_handle<T>(pid: u32, query: impl FnOnce(HANDLE) -> Option<T>) -> Option<T> {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let result = query(handle);
+    close_handle(handle);
+    result
+}
+
+/// Queries the full Win32 path of the executable and the architecture of the
+/// process through a single short-lived handle.
+pub(crate) fn process_details(pid: u32) -> (Option<PathBuf>, Option<Architecture>) {
+    with_limited_handle(pid, |handle| {
+        Some((path_of(handle), architecture_of(handle)))
+    })
+    .unwrap_or_default()
+}
+
+/// Returns the full Win32 path of the executable of the process behind `handle`.
+fn path_of(handle: HANDLE) -> Option<PathBuf> {
+    // Long path limit
+    let mut path = vec![0u16; 32_768];
+    let mut len = path.len() as u32;
+    unsafe {
+        QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(path.as_mut_ptr()),
+            addr_of_mut!(len),
+        )
+    }
+    .ok()?;
+    Some(OsString::from_wide(&path[..len as usize]).into())
+}
+
+/// Determines the architecture of the process via `IsWow64Process2`.
+pub(crate) fn process_architecture(pid: u32) -> Option<Architecture> {
+    with_limited_handle(pid, architecture_of)
+}
+
+/// Detects the architecture of an opened process for [`Process`](crate::Process),
+/// falling back to a short-lived handle if `handle` lacks the query right.
+pub(crate) fn architecture(handle: &RawHandle, pid: u32) -> Option<Architecture> {
+    architecture_of(*handle).or_else(|| process_architecture(pid))
+}
+
+/// Returns the architecture of the process behind `handle`, which needs at least
+/// `PROCESS_QUERY_LIMITED_INFORMATION`.
+fn architecture_of(handle: HANDLE) -> Option<Architecture> {
+    let mut process = IMAGE_FILE_MACHINE::default();
+    let mut native = IMAGE_FILE_MACHINE::default();
+    unsafe { IsWow64Process2(handle, addr_of_mut!(process), Some(addr_of_mut!(native))) }.ok()?;
+
+    // UNKNOWN means the process is not running under WOW64
+    let machine = if process == IMAGE_FILE_MACHINE_UNKNOWN {
+        native
+    } else {
+        process
+    };
+    match machine {
+        IMAGE_FILE_MACHINE_I386 => Some(Architecture::X86),
+        IMAGE_FILE_MACHINE_AMD64 => Some(Architecture::X86_64),
+        IMAGE_FILE_MACHINE_ARMNT => Some(Architecture::Arm),
+        IMAGE_FILE_MACHINE_ARM64 => Some(Architecture::Aarch64),
+        _ => None,
+    }
+}
+
+/// Fills a list through `fill`, growing the buffer until the whole list fits.
+///
+/// `fill` receives the buffer and returns the number of elements the complete list
+/// needs, as reported by Win32 list APIs such as `EnumProcessModules`, or `None` on
+/// failure. Lists can grow between two calls (a DLL gets loaded), so the buffer is
+/// grown with some headroom and `fill` retried until the reported size fits.
+fn grow_until_fits<T: Clone + Default>(
+    capacity: usize,
+    mut fill: impl FnMut(&mut [T]) -> Option<usize>,
+) -> Vec<T> {
+    let mut buffer = vec![T::default(); capacity];
+    loop {
+        let Some(needed) = fill(&mut buffer) else {
+            return Vec::new();
+        };
+        if needed <= buffer.len() {
+            buffer.truncate(needed);
+            return buffer;
+        }
+        buffer.resize(needed + needed / 2, T::default());
+    }
+}
+
+/// Maps Win32 page protection flags onto [`Protection`].
+fn protection(flags: PAGE_PROTECTION_FLAGS) -> Protection {
+    if flags.0 & (PAGE_GUARD.0 | PAGE_NOACCESS.0) != 0 {
+        return Protection::default();
+    }
+    let has = |mask: &[PAGE_PROTECTION_FLAGS]| mask.iter().any(|flag| flags.0 & flag.0 != 0);
+
+    Protection {
+        read: has(&[
+            PAGE_READONLY,
+            PAGE_READWRITE,
+            PAGE_WRITECOPY,
+            PAGE_EXECUTE_READ,
+            PAGE_EXECUTE_READWRITE,
+            PAGE_EXECUTE_WRITECOPY,
+        ]),
+        write: has(&[
+            PAGE_READWRITE,
+            PAGE_WRITECOPY,
+            PAGE_EXECUTE_READWRITE,
+            PAGE_EXECUTE_WRITECOPY,
+        ]),
+        execute: has(&[
+            PAGE_EXECUTE,
+            PAGE_EXECUTE_READ,
+            PAGE_EXECUTE_READWRITE,
+            PAGE_EXECUTE_WRITECOPY,
+        ]),
+    }
+}
+
+/// Win32 backend built on `ReadProcessMemory`, `WriteProcessMemory`,
+/// `VirtualQueryEx` and the PSAPI module functions.
+///
+/// The handle needs `PROCESS_VM_READ`, `PROCESS_VM_WRITE`, `PROCESS_VM_OPERATION`
+/// and `PROCESS_QUERY_INFORMATION` for the corresponding operations.
+impl MemoryAccess for HANDLE {
+    fn read_bytes(&self, addr: usize, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut transferred = 0usize;
+        let result = unsafe {
+            ReadProcessMemory(
+                *self,
+                addr as *const _,
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                Some(addr_of_mut!(transferred)),
+            )
+        };
+
+        match result {
+            Ok(()) => Ok(transferred),
+            // ERROR_PARTIAL_COPY still reports how much was copied
+            Err(_) if transferred > 0 => Ok(transferred),
+            Err(err) => Err(io_error(&err)),
+        }
+    }
+
+    fn write_bytes(&self, addr: usize, buffer: &[u8]) -> io::Result<usize> {
+        let mut transferred = 0usize;
+        let result = unsafe {
+            WriteProcessMemory(
+                *self,
+                addr as *const _,
+                buffer.as_ptr().cast(),
+                buffer.len(),
+                Some(addr_of_mut!(transferred)),
+            )
+        };
+
+        match result {
+            Ok(()) => Ok(transferred),
+            Err(_) if transferred > 0 => Ok(transferred),
+            Err(err) => Err(io_error(&err)),
+        }
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        let mut regions = Vec::new();
+        let mut mbi = MEMORY_BASIC_INFORMATION::default();
+        let mut address = null();
+
+        while unsafe {
+            VirtualQueryEx(
+                *self,
+                Some(address),
+                addr_of_mut!(mbi),
+                size_of::<MEMORY_BASIC_INFORMATION>(),
+            )
+        } != 0
+        {
+            if mbi.State == MEM_COMMIT {
+                regions.push(MemoryRegion {
+                    base: mbi.BaseAddress as usize,
+                    size: mbi.RegionSize,
+                    protection: protection(mbi.Protect),
+                });
+            }
+
+            let next = (mbi.BaseAddress as usize).wrapping_add(mbi.RegionSize);
+            if next <= address as usize {
+                break;
+            }
+            address = next as *const _;
+        }
+        regions
+    }
+
+    fn modules(&self) -> Vec<ModuleData> {
+        // A WOW64 target also maps the 64-bit ntdll and WOW64 layer, which its own
+        // code never uses; list only its 32-bit modules, which EnumProcessModules
+        // hides from 64-bit callers
+        let filter = match architecture_of(*self) {
+            Some(Architecture::X86 | Architecture::Arm) => LIST_MODULES_32BIT,
+            _ => LIST_MODULES_DEFAULT,
+        };
+        let mod_list = grow_until_fits(256, |mod_list: &mut [HMODULE]| {
+            let mut cb_needed = 0;
+            unsafe {
+                EnumProcessModulesEx(
+                    *self,
+                    mod_list.as_mut_ptr(),
+                    u32::try_from(size_of_val(mod_list)).ok()?,
+                    addr_of_mut!(cb_needed),
+                    filter,
+                )
+            }
+            .ok()?;
+            Some(cb_needed as usize / size_of::<HMODULE>())
+        });
+
+        mod_list
+            .iter()
+            .map(|&mod_handle| {
+                let mut name = [0u8; 256];
+                let mut mi = MODULEINFO::default();
+
+                unsafe {
+                    let _ = GetModuleBaseNameA(*self, Some(mod_handle), &mut name);
+                    let _ = GetModuleInformation(
+                        *self,
+                        mod_handle,
+                        addr_of_mut!(mi),
+                        size_of::<MODULEINFO>() as u32,
+                    );
+                }
+
+                ModuleData {
+                    module_name: name
+                        .to_string_lowercase()
+                        .unwrap_or("<Module Name>".to_string()),
+                    module_addr: mi.lpBaseOfDll as usize,
+                    module_size: mi.SizeOfImage as usize,
+                }
+            })
+            .collect()
+    }
+
+    fn pid(&self) -> Option<u32> {
+        match unsafe { GetProcessId(*self) } {
+            0 => None,
+            pid => Some(pid),
+        }
+    }
+
+    /// Asks `IsWow64Process2` on every call, as a raw handle has nowhere to keep the
+    /// answer; [`Process`](crate::Process) detects the architecture once and caches it.
+    fn architecture(&self) -> Option<Architecture> {
+        architecture_of(*self)
+    }
+}